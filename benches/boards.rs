@@ -0,0 +1,43 @@
+// Timings for the board-heavy days on large generated inputs.
+// Run with `cargo bench --bench boards`.
+use aoc25::board::bits::BitBoard;
+use aoc25::board::Board;
+use aoc25::days::{day04, day07};
+use aoc25::generator::generate;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+// Best of `runs`, so a noisy machine does not hide a regression
+fn bench<R>(name: &str, runs: usize, mut run: impl FnMut() -> R) {
+    let mut best = Duration::MAX;
+    for _ in 0..runs {
+        let start = Instant::now();
+        black_box(run());
+        best = best.min(start.elapsed());
+    }
+    println!("{:<10} {:>10.3} ms", name, best.as_secs_f64() * 1000.0);
+}
+
+// Cells of `board` with fewer than four rolls around them, one cell at a time
+fn few_neighbours(board: &Board<char>) -> usize {
+    (0..board.cells().len())
+        .filter(|&idx| board.neighbors8(board.coord_of(idx)).filter(|&(_, &c)| c == '@').count() < 4)
+        .count()
+}
+
+// The same cells, counted for the whole board at once
+fn few_neighbours_bits(board: &Board<char>) -> usize {
+    BitBoard::from_board(board, |&c| c == '@').neighbour_counts().fewer_than(4).count_ones()
+}
+
+fn main() {
+    let day04_input = generate(4, 400, 4).unwrap();
+    let day07_input = generate(7, 1000, 7).unwrap();
+    let rolls = Board::<char>::from_string(&day04_input).unwrap();
+    assert_eq!(few_neighbours(&rolls), few_neighbours_bits(&rolls));
+
+    bench("day04", 20, || day04::answers(black_box(&day04_input)).unwrap());
+    bench("day07", 20, || day07::answers(black_box(&day07_input)).unwrap());
+    bench("neighbors8", 20, || few_neighbours(black_box(&rolls)));
+    bench("bitboard", 20, || few_neighbours_bits(black_box(&rolls)));
+}
@@ -0,0 +1,179 @@
+use crate::bignum::{BigUint, Count};
+use anyhow::{bail, Result};
+use std::fmt::{Display, Formatter};
+
+// What a solver returns for one part. `BigInteger` only holds values that do not
+// fit in an i64, so two equal answers always have the same variant.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Integer(i64),
+    BigInteger(BigUint),
+    Text(String),
+    Unsolved,
+}
+
+impl Answer {
+    // JSON value: a number, a string or null
+    pub fn to_json(&self) -> String {
+        match self {
+            Answer::Integer(value) => value.to_string(),
+            Answer::BigInteger(value) => value.to_string(),
+            Answer::Text(text) => {
+                let mut json = String::from("\"");
+                for c in text.chars() {
+                    match c {
+                        '"' => json.push_str("\\\""),
+                        '\\' => json.push_str("\\\\"),
+                        '\n' => json.push_str("\\n"),
+                        c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+                        c => json.push(c),
+                    }
+                }
+                json.push('"');
+                json
+            }
+            Answer::Unsolved => "null".to_string(),
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self> {
+        let json = json.trim();
+        if json == "null" {
+            return Ok(Answer::Unsolved);
+        }
+        if let Some(inner) = json.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+            let mut text = String::new();
+            let mut chars = inner.chars();
+            while let Some(c) = chars.next() {
+                if c != '\\' {
+                    text.push(c);
+                    continue;
+                }
+                match chars.next() {
+                    Some('"') => text.push('"'),
+                    Some('\\') => text.push('\\'),
+                    Some('n') => text.push('\n'),
+                    Some('u') => {
+                        let code: String = chars.by_ref().take(4).collect();
+                        let Some(c) = u32::from_str_radix(&code, 16).ok().and_then(char::from_u32) else {
+                            bail!("Invalid escape \\u{} in {}", code, json);
+                        };
+                        text.push(c);
+                    }
+                    other => bail!("Invalid escape {:?} in {}", other, json),
+                }
+            }
+            return Ok(Answer::Text(text));
+        }
+        if let Ok(value) = json.parse::<i64>() {
+            return Ok(Answer::Integer(value));
+        }
+        match json.parse::<BigUint>() {
+            Ok(value) => Ok(Answer::BigInteger(value)),
+            Err(_) => bail!("Invalid answer {}", json),
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(value) => write!(f, "{}", value),
+            Answer::BigInteger(value) => write!(f, "{}", value),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::Unsolved => write!(f, "unsolved"),
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(value: i32) -> Self {
+        Answer::Integer(value as i64)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        Answer::Integer(value)
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(value: u32) -> Self {
+        Answer::Integer(value as i64)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        match i64::try_from(value) {
+            Ok(value) => Answer::Integer(value),
+            Err(_) => Answer::BigInteger(BigUint::from(value)),
+        }
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::from(value as u64)
+    }
+}
+
+impl From<BigUint> for Answer {
+    fn from(value: BigUint) -> Self {
+        match value.to_u64() {
+            Some(small) => Answer::from(small),
+            None => Answer::BigInteger(value),
+        }
+    }
+}
+
+impl From<Count> for Answer {
+    fn from(value: Count) -> Self {
+        match value {
+            Count::Small(value) => Answer::from(value),
+            Count::Big(value) => Answer::BigInteger(value),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answer::Answer;
+    use crate::bignum::{BigUint, Count};
+
+    #[test]
+    fn test_answers_compare_across_types() {
+        assert_eq!(Answer::from(40i32), Answer::from(40u64));
+        assert_eq!(Answer::from(Count::from(7)), Answer::from(BigUint::from(7)));
+        assert!(matches!(Answer::from(u64::MAX), Answer::BigInteger(_)));
+        assert_eq!(Answer::from(u64::MAX).to_string(), "18446744073709551615");
+        assert_eq!(Answer::Unsolved.to_string(), "unsolved");
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let answers = [
+            Answer::from(-12i64),
+            Answer::from(u64::MAX),
+            Answer::from("a \"quoted\"\\ line\n\u{1}"),
+            Answer::Unsolved,
+        ];
+        for answer in answers {
+            assert_eq!(Answer::from_json(&answer.to_json()).unwrap(), answer);
+        }
+        assert!(Answer::from_json("12a").is_err());
+    }
+}
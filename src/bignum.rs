@@ -0,0 +1,272 @@
+use anyhow::{bail, Result};
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::iter::{Product, Sum};
+use std::ops::{Add, Mul};
+use std::str::FromStr;
+
+// Unsigned integer of any size, stored as base 2^32 limbs, least significant first.
+// There are never trailing zero limbs, so zero is an empty vector.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    pub fn zero() -> Self {
+        Self { limbs: vec![] }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.limbs.len() {
+            0 => Some(0),
+            1 => Some(self.limbs[0] as u64),
+            2 => Some(self.limbs[0] as u64 | (self.limbs[1] as u64) << 32),
+            _ => None,
+        }
+    }
+
+    fn trim(mut self) -> Self {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+        self
+    }
+
+    // Divides in place by a small divisor and returns the remainder
+    fn div_rem_small(&mut self, divisor: u32) -> u32 {
+        let mut remainder: u64 = 0;
+        for limb in self.limbs.iter_mut().rev() {
+            let current = remainder << 32 | *limb as u64;
+            *limb = (current / divisor as u64) as u32;
+            remainder = current % divisor as u64;
+        }
+        *self = std::mem::take(self).trim();
+        remainder as u32
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> Self {
+        Self { limbs: vec![value as u32, (value >> 32) as u32] }.trim()
+    }
+}
+
+impl Add for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let len = self.limbs.len().max(rhs.limbs.len());
+        let mut limbs = Vec::with_capacity(len + 1);
+        let mut carry: u64 = 0;
+        for i in 0..len {
+            let a = *self.limbs.get(i).unwrap_or(&0) as u64;
+            let b = *rhs.limbs.get(i).unwrap_or(&0) as u64;
+            let sum = a + b + carry;
+            limbs.push(sum as u32);
+            carry = sum >> 32;
+        }
+        limbs.push(carry as u32);
+        BigUint { limbs }.trim()
+    }
+}
+
+impl Mul for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let mut limbs = vec![0u32; self.limbs.len() + rhs.limbs.len()];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry: u64 = 0;
+            for (j, &b) in rhs.limbs.iter().enumerate() {
+                let current = limbs[i + j] as u64 + a as u64 * b as u64 + carry;
+                limbs[i + j] = current as u32;
+                carry = current >> 32;
+            }
+            limbs[i + rhs.limbs.len()] = carry as u32;
+        }
+        BigUint { limbs }.trim()
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for BigUint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        // Peel off nine decimal digits at a time
+        let mut rest = self.clone();
+        let mut chunks: Vec<u32> = Vec::new();
+        while !rest.is_zero() {
+            chunks.push(rest.div_rem_small(1_000_000_000));
+        }
+        write!(f, "{}", chunks.last().unwrap())?;
+        for chunk in chunks.iter().rev().skip(1) {
+            write!(f, "{:09}", chunk)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for BigUint {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if s.is_empty() {
+            bail!("Empty number");
+        }
+        let ten = BigUint::from(10);
+        let mut result = BigUint::zero();
+        for c in s.chars() {
+            let Some(digit) = c.to_digit(10) else {
+                bail!("Invalid digit {:?} in {:?}", c, s);
+            };
+            result = &(&result * &ten) + &BigUint::from(digit as u64);
+        }
+        Ok(result)
+    }
+}
+
+// A count that stays a plain u64 while it fits and moves to a BigUint when
+// an addition or a multiplication would overflow. `Big` only ever holds values
+// above u64::MAX, so the derived equality is exact.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Count {
+    Small(u64),
+    Big(BigUint),
+}
+
+impl Count {
+    fn from_big(value: BigUint) -> Self {
+        match value.to_u64() {
+            Some(small) => Count::Small(small),
+            None => Count::Big(value),
+        }
+    }
+
+    fn to_big(&self) -> BigUint {
+        match self {
+            Count::Small(value) => BigUint::from(*value),
+            Count::Big(value) => value.clone(),
+        }
+    }
+}
+
+impl Default for Count {
+    fn default() -> Self {
+        Count::Small(0)
+    }
+}
+
+impl From<u64> for Count {
+    fn from(value: u64) -> Self {
+        Count::Small(value)
+    }
+}
+
+impl From<BigUint> for Count {
+    fn from(value: BigUint) -> Self {
+        Count::from_big(value)
+    }
+}
+
+impl Add for Count {
+    type Output = Count;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        match (&self, &rhs) {
+            (Count::Small(a), Count::Small(b)) => match a.checked_add(*b) {
+                Some(sum) => Count::Small(sum),
+                None => Count::from_big(&self.to_big() + &rhs.to_big()),
+            },
+            _ => Count::from_big(&self.to_big() + &rhs.to_big()),
+        }
+    }
+}
+
+impl Mul for Count {
+    type Output = Count;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        match (&self, &rhs) {
+            (Count::Small(a), Count::Small(b)) => match a.checked_mul(*b) {
+                Some(product) => Count::Small(product),
+                None => Count::from_big(&self.to_big() * &rhs.to_big()),
+            },
+            _ => Count::from_big(&self.to_big() * &rhs.to_big()),
+        }
+    }
+}
+
+impl Sum for Count {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Count::Small(0), |acc, x| acc + x)
+    }
+}
+
+impl Product for Count {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Count::Small(1), |acc, x| acc * x)
+    }
+}
+
+impl PartialEq<u64> for Count {
+    fn eq(&self, other: &u64) -> bool {
+        *self == Count::Small(*other)
+    }
+}
+
+impl Display for Count {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Count::Small(value) => write!(f, "{}", value),
+            Count::Big(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bignum::{BigUint, Count};
+
+    #[test]
+    fn test_big_uint_arithmetic() {
+        let a: BigUint = "340282366920938463463374607431768211455".parse().unwrap();
+        let one = BigUint::from(1);
+        assert_eq!((&a + &one).to_string(), "340282366920938463463374607431768211456");
+        let b = BigUint::from(u64::MAX);
+        assert_eq!((&b * &b).to_string(), "340282366920938463426481119284349108225");
+        assert!(a > b);
+        assert_eq!(BigUint::from(0).to_string(), "0");
+    }
+
+    #[test]
+    fn test_count_promotes_on_overflow() {
+        let max = Count::from(u64::MAX);
+        let sum = max.clone() + Count::from(1);
+        assert!(matches!(sum, Count::Big(_)));
+        assert_eq!(sum.to_string(), "18446744073709551616");
+        let product: Count = [max.clone(), max].into_iter().product();
+        assert_eq!(product.to_string(), "340282366920938463426481119284349108225");
+        assert_eq!([1u64, 2, 3].into_iter().map(Count::from).sum::<Count>(), 6);
+    }
+}
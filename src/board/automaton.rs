@@ -0,0 +1,148 @@
+use crate::board::{Board, Coord, NEIGHBORS8};
+use std::fmt::{Debug, Display};
+
+// Synchronous steps compute every cell from the previous board. Asynchronous steps
+// update cells in reading order, so later cells already see this step's changes
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UpdateMode {
+    Synchronous,
+    Asynchronous,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct StepStats {
+    pub step: usize,
+    pub changed: usize,
+}
+
+// The in-bounds neighbours of one cell, read only when the rule asks for them
+pub struct Neighbourhood<'b, T> {
+    board: &'b Board<T>,
+    coord: Coord,
+    stencil: &'b [Coord],
+}
+
+impl<'b, T> Neighbourhood<'b, T>
+where
+    T: PartialEq + Debug + Display + Copy,
+{
+    pub fn coord(&self) -> Coord {
+        self.coord
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'b T> {
+        self.board.neighbors(self.coord, self.stencil).map(|(_, value)| value)
+    }
+
+    pub fn count(&self, value: T) -> usize {
+        self.iter().filter(|&&v| v == value).count()
+    }
+}
+
+// New value of a cell from its current value and its neighbourhood
+type Rule<'a, T> = Box<dyn Fn(&T, &Neighbourhood<T>) -> T + 'a>;
+
+pub struct Automaton<'a, T> {
+    rule: Rule<'a, T>,
+    stencil: &'a [Coord],
+    mode: UpdateMode,
+}
+
+impl<'a, T> Automaton<'a, T>
+where
+    T: PartialEq + Debug + Display + Copy,
+{
+    // Eight neighbours and synchronous updates unless told otherwise
+    pub fn new(rule: impl Fn(&T, &Neighbourhood<T>) -> T + 'a) -> Self {
+        Self { rule: Box::new(rule), stencil: &NEIGHBORS8, mode: UpdateMode::Synchronous }
+    }
+
+    pub fn stencil(mut self, stencil: &'a [Coord]) -> Self {
+        self.stencil = stencil;
+        self
+    }
+
+    pub fn mode(mut self, mode: UpdateMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    fn next_value(&self, board: &Board<T>, idx: usize) -> T {
+        let neighbourhood = Neighbourhood { board, coord: board.coord_of(idx), stencil: self.stencil };
+        (self.rule)(&board.cells()[idx], &neighbourhood)
+    }
+
+    // Applies the rule once to every cell and counts the cells that changed
+    pub fn step(&self, board: &mut Board<T>) -> usize {
+        let mut changed = 0;
+        match self.mode {
+            UpdateMode::Synchronous => {
+                let next: Vec<T> = (0..board.cells.len())
+                    .map(|idx| self.next_value(board, idx))
+                    .collect();
+                changed = next.iter().zip(&board.cells).filter(|(new, old)| new != old).count();
+                board.cells = next;
+            }
+            UpdateMode::Asynchronous => {
+                for idx in 0..board.cells.len() {
+                    let value = self.next_value(board, idx);
+                    if value != board.cells[idx] {
+                        board.cells[idx] = value;
+                        changed += 1;
+                    }
+                }
+            }
+        }
+        changed
+    }
+
+    pub fn run(&self, board: &mut Board<T>, steps: usize) -> Vec<StepStats> {
+        (1..=steps)
+            .map(|step| StepStats { step, changed: self.step(board) })
+            .collect()
+    }
+
+    // Steps until nothing changes. The last entry is the step that changed nothing
+    pub fn run_until_stable(&self, board: &mut Board<T>) -> Vec<StepStats> {
+        let mut stats = vec![];
+        loop {
+            let changed = self.step(board);
+            stats.push(StepStats { step: stats.len() + 1, changed });
+            if changed == 0 {
+                return stats;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board::automaton::{Automaton, Neighbourhood, UpdateMode};
+    use crate::board::{Board, NEIGHBORS4};
+
+    // Every cell next to a '#' becomes '#'
+    fn spread(cell: &char, neighbours: &Neighbourhood<char>) -> char {
+        if neighbours.count('#') > 0 { '#' } else { *cell }
+    }
+
+    #[test]
+    fn test_synchronous_spread() {
+        let mut board = Board::<char>::from_string("#....\n").unwrap();
+        let automaton = Automaton::new(spread).stencil(&NEIGHBORS4);
+        let stats = automaton.run(&mut board, 2);
+        assert_eq!(stats.iter().map(|s| s.changed).collect::<Vec<_>>(), vec![1, 1]);
+        assert_eq!(board.to_string_compact(), "###..\n");
+
+        let stats = automaton.run_until_stable(&mut board);
+        assert_eq!(stats.iter().map(|s| s.changed).collect::<Vec<_>>(), vec![1, 1, 0]);
+        assert_eq!(stats.last().unwrap().step, 3);
+    }
+
+    #[test]
+    fn test_asynchronous_sees_earlier_updates() {
+        let mut board = Board::<char>::from_string("#....\n").unwrap();
+        let automaton = Automaton::new(spread).stencil(&NEIGHBORS4).mode(UpdateMode::Asynchronous);
+        assert_eq!(automaton.step(&mut board), 4);
+        assert_eq!(board.to_string_compact(), "#####\n");
+    }
+}
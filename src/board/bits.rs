@@ -0,0 +1,313 @@
+use crate::board::{axis_index, Board, Coord, Wrap, NEIGHBORS8};
+use std::fmt::{Debug, Display};
+use std::ops::{BitAnd, BitOr, BitXor, Not};
+
+// Boolean board with one bit per cell. Each row starts on a new u64 word, bit x % 64
+// of word x / 64 holds column x, and bits past the width are always zero.
+// Wrapping axes behave as they do on `Board`, shifts included.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct BitBoard {
+    words: Vec<u64>,
+    width: usize,
+    height: usize,
+    words_per_row: usize,
+    wrap: Wrap,
+}
+
+impl BitBoard {
+    pub fn new(width: usize, height: usize) -> Self {
+        let words_per_row = width.div_ceil(64);
+        Self { words: vec![0; words_per_row * height], width, height, words_per_row, wrap: Wrap::NONE }
+    }
+
+    pub fn with_wrap(mut self, wrap: Wrap) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    pub fn from_board<T>(board: &Board<T>, predicate: impl Fn(&T) -> bool) -> Self
+    where
+        T: PartialEq + Debug + Display + Copy,
+    {
+        let mut bits = Self::new(board.width(), board.height()).with_wrap(board.wrap());
+        for (idx, value) in board.cells().iter().enumerate() {
+            if predicate(value) {
+                bits.set(board.coord_of(idx), true);
+            }
+        }
+        bits
+    }
+
+    pub fn to_board<T>(&self, on: T, off: T) -> Board<T>
+    where
+        T: PartialEq + Debug + Display + Copy,
+    {
+        let mut board = Board::filled(self.width, self.height, off).with_wrap(self.wrap);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let coord = Coord::new(x as i32, y as i32);
+                if self.get(coord) {
+                    board.set_value(coord, on);
+                }
+            }
+        }
+        board
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn wrap(&self) -> Wrap {
+        self.wrap
+    }
+
+    // Word index and bit mask of `coord`, if it is on the board
+    fn locate(&self, coord: Coord) -> Option<(usize, u64)> {
+        let x = axis_index(coord.x(), self.width, self.wrap.x)?;
+        let y = axis_index(coord.y(), self.height, self.wrap.y)?;
+        Some((y * self.words_per_row + x / 64, 1 << (x % 64)))
+    }
+
+    // Cells off the board read as false
+    pub fn get(&self, coord: Coord) -> bool {
+        self.locate(coord).is_some_and(|(word, mask)| self.words[word] & mask != 0)
+    }
+
+    pub fn set(&mut self, coord: Coord, value: bool) {
+        let (word, mask) = self
+            .locate(coord)
+            .unwrap_or_else(|| panic!("({}, {}) is outside the board", coord.x(), coord.y()));
+        if value {
+            self.words[word] |= mask;
+        } else {
+            self.words[word] &= !mask;
+        }
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    fn row(&self, y: usize) -> &[u64] {
+        &self.words[y * self.words_per_row..(y + 1) * self.words_per_row]
+    }
+
+    // Clears the bits past the width in the last word of every row
+    fn clear_padding(&mut self) {
+        if self.width.is_multiple_of(64) {
+            return;
+        }
+        let mask = (1u64 << (self.width % 64)) - 1;
+        for y in 0..self.height {
+            self.words[(y + 1) * self.words_per_row - 1] &= mask;
+        }
+    }
+
+    // Every cell moved by (dx, dy). Cells pushed off a wrapping axis come back on
+    // the other side; otherwise they are lost and the cells left behind are false
+    pub fn shifted(&self, dx: i32, dy: i32) -> Self {
+        if self.width == 0 || !(self.wrap.x || self.wrap.y) {
+            return self.shifted_flat(dx, dy);
+        }
+        // The cells that fall off one side are the ones a shift one lap back brings in
+        let laps = |delta: i32, size: usize, wrap: bool| match wrap {
+            true => vec![delta.rem_euclid(size as i32), delta.rem_euclid(size as i32) - size as i32],
+            false => vec![delta],
+        };
+        let mut result = Self::new(self.width, self.height).with_wrap(self.wrap);
+        for sx in laps(dx, self.width, self.wrap.x) {
+            for &sy in &laps(dy, self.height, self.wrap.y) {
+                result = &result | &self.shifted_flat(sx, sy);
+            }
+        }
+        result
+    }
+
+    fn shifted_flat(&self, dx: i32, dy: i32) -> Self {
+        let mut result = Self::new(self.width, self.height).with_wrap(self.wrap);
+        let (word_shift, bit_shift) = (dx.unsigned_abs() as usize / 64, dx.unsigned_abs() % 64);
+        for y in 0..self.height {
+            let source_y = y as i64 - dy as i64;
+            if source_y < 0 || source_y >= self.height as i64 {
+                continue;
+            }
+            let source = self.row(source_y as usize);
+            let at = |i: Option<usize>| i.and_then(|i| source.get(i)).copied().unwrap_or(0);
+            for i in 0..self.words_per_row {
+                let word = if dx >= 0 {
+                    let low = at(i.checked_sub(word_shift)) << bit_shift;
+                    let carry = match bit_shift {
+                        0 => 0,
+                        _ => at(i.checked_sub(word_shift + 1)) >> (64 - bit_shift),
+                    };
+                    low | carry
+                } else {
+                    let high = at(Some(i + word_shift)) >> bit_shift;
+                    let carry = match bit_shift {
+                        0 => 0,
+                        _ => at(Some(i + word_shift + 1)) << (64 - bit_shift),
+                    };
+                    high | carry
+                };
+                result.words[y * self.words_per_row + i] = word;
+            }
+        }
+        result.clear_padding();
+        result
+    }
+
+    fn zip_words(&self, other: &Self, op: impl Fn(u64, u64) -> u64) -> Self {
+        assert_eq!((self.width, self.height), (other.width, other.height), "Bit boards of different sizes");
+        let words = self.words.iter().zip(&other.words).map(|(&a, &b)| op(a, b)).collect();
+        Self { words, ..*self }
+    }
+
+    pub fn and_not(&self, other: &Self) -> Self {
+        self.zip_words(other, |a, b| a & !b)
+    }
+
+    // How many of the eight surrounding cells are set, for every cell at once
+    pub fn neighbour_counts(&self) -> NeighbourCounts {
+        // Bit-sliced counter: planes[i] holds bit i of each cell's count
+        let mut planes: [BitBoard; 4] = std::array::from_fn(|_| Self::new(self.width, self.height).with_wrap(self.wrap));
+        for offset in NEIGHBORS8 {
+            let mut carry = self.shifted(-offset.x(), -offset.y());
+            for plane in planes.iter_mut() {
+                let sum = &*plane ^ &carry;
+                carry = &*plane & &carry;
+                *plane = sum;
+            }
+        }
+        NeighbourCounts { planes }
+    }
+}
+
+impl BitAnd for &BitBoard {
+    type Output = BitBoard;
+
+    fn bitand(self, rhs: Self) -> BitBoard {
+        self.zip_words(rhs, |a, b| a & b)
+    }
+}
+
+impl BitOr for &BitBoard {
+    type Output = BitBoard;
+
+    fn bitor(self, rhs: Self) -> BitBoard {
+        self.zip_words(rhs, |a, b| a | b)
+    }
+}
+
+impl BitXor for &BitBoard {
+    type Output = BitBoard;
+
+    fn bitxor(self, rhs: Self) -> BitBoard {
+        self.zip_words(rhs, |a, b| a ^ b)
+    }
+}
+
+impl Not for &BitBoard {
+    type Output = BitBoard;
+
+    fn not(self) -> BitBoard {
+        let mut result = BitBoard { words: self.words.iter().map(|&word| !word).collect(), ..*self };
+        result.clear_padding();
+        result
+    }
+}
+
+// Neighbour count of every cell, from 0 to 8, stored as four bit planes
+pub struct NeighbourCounts {
+    planes: [BitBoard; 4],
+}
+
+impl NeighbourCounts {
+    // Cells whose count is exactly `n`
+    pub fn exactly(&self, n: usize) -> BitBoard {
+        let none = BitBoard::new(self.planes[0].width, self.planes[0].height).with_wrap(self.planes[0].wrap);
+        // The planes only hold four bits, so larger `n` would match by its low bits
+        if n > 8 {
+            return none;
+        }
+        let mut result = !&none;
+        for (bit, plane) in self.planes.iter().enumerate() {
+            result = if n & (1 << bit) != 0 { &result & plane } else { result.and_not(plane) };
+        }
+        result
+    }
+
+    pub fn fewer_than(&self, n: usize) -> BitBoard {
+        let none = BitBoard::new(self.planes[0].width, self.planes[0].height).with_wrap(self.planes[0].wrap);
+        (0..n.min(9)).fold(none, |acc, k| {
+            &acc | &self.exactly(k)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board::bits::BitBoard;
+    use crate::board::{Board, Coord, Wrap};
+    use crate::generator::Rng;
+
+    #[test]
+    fn test_shifts_and_word_ops() {
+        let board = Board::<char>::from_string("#..#\n.#..\n").unwrap();
+        let bits = BitBoard::from_board(&board, |&c| c == '#');
+        assert_eq!(bits.count_ones(), 3);
+        assert_eq!(bits.shifted(1, 0).to_board('#', '.').to_string_compact(), ".#..\n..#.\n");
+        assert_eq!(bits.shifted(-1, 1).to_board('#', '.').to_string_compact(), "....\n..#.\n");
+        assert_eq!((&bits ^ &bits.shifted(0, 0)).count_ones(), 0);
+        assert_eq!((!&bits).count_ones(), 5);
+        assert_eq!(bits.and_not(&bits.shifted(3, 0)).count_ones(), 2);
+
+        // Shifts that cross word boundaries
+        let mut wide = BitBoard::new(150, 1);
+        wide.set(Coord::new(63, 0), true);
+        assert!(wide.shifted(1, 0).get(Coord::new(64, 0)));
+        assert!(wide.shifted(70, 0).get(Coord::new(133, 0)));
+        assert!(wide.shifted(-63, 0).get(Coord::new(0, 0)));
+        assert!(wide.shifted(87, 0).is_empty());
+
+        // Wrapping shifts bring the cells back in on the other side
+        let ring = BitBoard::from_board(&board.clone().with_wrap(Wrap::BOTH), |&c| c == '#');
+        assert_eq!(ring.shifted(1, 0).to_board('#', '.').to_string_compact(), "##..\n..#.\n");
+        assert_eq!(ring.shifted(-5, 1).to_board('#', '.').to_string_compact(), "#...\n..##\n");
+        let wide = wide.with_wrap(Wrap { x: true, y: false });
+        assert!(wide.shifted(-64, 0).get(Coord::new(149, 0)));
+    }
+
+    #[test]
+    fn test_neighbour_counts_match_board() {
+        let mut rng = Rng::new(46);
+        let text: String = (0..9)
+            .map(|_| (0..100).map(|_| if rng.chance(1, 2) { '@' } else { '.' }).collect::<String>() + "\n")
+            .collect();
+        for wrap in [Wrap::NONE, Wrap { x: true, y: false }, Wrap::BOTH] {
+            let board = Board::<char>::from_string(&text).unwrap().with_wrap(wrap);
+            let counts = BitBoard::from_board(&board, |&c| c == '@').neighbour_counts();
+            for n in 0..=9 {
+                let exact = counts.exactly(n);
+                let fewer = counts.fewer_than(n);
+                assert_eq!((exact.wrap(), fewer.wrap()), (wrap, wrap));
+                for (idx, _) in board.cells().iter().enumerate() {
+                    let coord = board.coord_of(idx);
+                    let around = board.neighbors8(coord).filter(|&(_, &c)| c == '@').count();
+                    assert_eq!(exact.get(coord), around == n);
+                    assert_eq!(fewer.get(coord), around < n);
+                }
+            }
+            // Counts only have four bits, 16 must not alias to 0
+            assert!(counts.exactly(16).is_empty());
+        }
+    }
+}
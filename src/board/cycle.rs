@@ -0,0 +1,90 @@
+use crate::board::Board;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fmt::{Debug, Display};
+use std::hash::{Hash, Hasher};
+
+// Every state seen so far, where states[i] is the board after i steps
+struct History<T> {
+    states: Vec<Board<T>>,
+    by_hash: HashMap<u64, Vec<usize>>,
+}
+
+impl<T> History<T>
+where
+    T: PartialEq + Debug + Display + Copy + Hash,
+{
+    // Steps until `last_step` is reached or a state repeats, returning (cycle_start, cycle_length)
+    fn simulate(initial: &Board<T>, mut step: impl FnMut(&mut Board<T>), last_step: usize) -> (Self, Option<(usize, usize)>) {
+        let mut history = Self { states: vec![], by_hash: HashMap::new() };
+        let mut board = initial.clone();
+        loop {
+            let mut hasher = DefaultHasher::new();
+            board.hash(&mut hasher);
+            let seen = history.by_hash.entry(hasher.finish()).or_default();
+            let now = history.states.len();
+            if let Some(&start) = seen.iter().find(|&&idx| history.states[idx] == board) {
+                return (history, Some((start, now - start)));
+            }
+            seen.push(now);
+            history.states.push(board.clone());
+            if now == last_step {
+                return (history, None);
+            }
+            step(&mut board);
+        }
+    }
+}
+
+// First step whose board appears again later, and how many steps it takes to come back.
+// None if no state repeats within `max_steps` steps
+pub fn find_cycle<T>(initial: &Board<T>, step: impl FnMut(&mut Board<T>), max_steps: usize) -> Option<(usize, usize)>
+where
+    T: PartialEq + Debug + Display + Copy + Hash,
+{
+    History::simulate(initial, step, max_steps).1
+}
+
+// The board after `n` steps, skipping whole cycles once one is found
+pub fn fast_forward<T>(initial: &Board<T>, step: impl FnMut(&mut Board<T>), n: usize) -> Board<T>
+where
+    T: PartialEq + Debug + Display + Copy + Hash,
+{
+    let (mut history, cycle) = History::simulate(initial, step, n);
+    let idx = match cycle {
+        Some((start, length)) => start + (n - start) % length,
+        None => n,
+    };
+    history.states.swap_remove(idx)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board::automaton::Automaton;
+    use crate::board::cycle::{fast_forward, find_cycle};
+    use crate::board::{Board, NEIGHBORS4};
+
+    #[test]
+    fn test_rotation_cycles() {
+        let board = Board::<char>::from_string("#..\n...\n..#\n").unwrap();
+        let rotate = |b: &mut Board<char>| *b = b.rotate_cw();
+        assert_eq!(find_cycle(&board, rotate, 100), Some((0, 2)));
+        assert_eq!(find_cycle(&board, rotate, 1), None);
+
+        let corner = Board::<char>::from_string("#..\n...\n...\n").unwrap();
+        assert_eq!(find_cycle(&corner, rotate, 100), Some((0, 4)));
+        assert_eq!(fast_forward(&corner, rotate, 1_000_000_001), corner.rotate_cw());
+        assert_eq!(fast_forward(&corner, rotate, 3), corner.rotate_ccw());
+    }
+
+    #[test]
+    fn test_stable_simulation() {
+        let board = Board::<char>::from_string("#...\n").unwrap();
+        let spread = Automaton::new(|c, n| if n.count('#') > 0 { '#' } else { *c }).stencil(&NEIGHBORS4);
+        let step = |b: &mut Board<char>| {
+            spread.step(b);
+        };
+        assert_eq!(find_cycle(&board, step, 100), Some((3, 1)));
+        assert_eq!(fast_forward(&board, step, 1_000_000_000).to_string_compact(), "####\n");
+    }
+}
@@ -0,0 +1,121 @@
+use crate::board::render::paint;
+use crate::board::{Board, Coord};
+use std::fmt::{Debug, Display};
+use std::ops::Range;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CellChange<T> {
+    pub coord: Coord,
+    pub old: T,
+    pub new: T,
+}
+
+// Cells are compared where both boards overlap. Rows and columns that only exist
+// in the new board are added, those only in the old board are removed
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BoardDiff<T> {
+    pub changed: Vec<CellChange<T>>,
+    pub added_rows: Range<usize>,
+    pub removed_rows: Range<usize>,
+    pub added_columns: Range<usize>,
+    pub removed_columns: Range<usize>,
+}
+
+impl<T> BoardDiff<T> {
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty()
+            && self.added_rows.is_empty()
+            && self.removed_rows.is_empty()
+            && self.added_columns.is_empty()
+            && self.removed_columns.is_empty()
+    }
+}
+
+impl<T> Board<T>
+where
+    T: PartialEq + Debug + Display + Copy,
+{
+    // What changed going from `self` to `other`
+    pub fn diff(&self, other: &Board<T>) -> BoardDiff<T> {
+        let width = self.width().min(other.width());
+        let height = self.height().min(other.height());
+        let changed = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .filter_map(|(x, y)| {
+                let (old, new) = (self.row(y)[x], other.row(y)[x]);
+                (old != new).then_some(CellChange { coord: Coord::new(x as i32, y as i32), old, new })
+            })
+            .collect();
+        BoardDiff {
+            changed,
+            added_rows: height..other.height(),
+            removed_rows: height..self.height(),
+            added_columns: width..other.width(),
+            removed_columns: width..self.width(),
+        }
+    }
+
+    // Both boards next to each other. Cells that differ, or have no counterpart in the
+    // other board, are highlighted when `colour` is set
+    pub fn render_side_by_side(&self, other: &Board<T>, colour: bool) -> String {
+        let line = |board: &Board<T>, against: &Board<T>, y: usize| -> (String, usize) {
+            if y >= board.height() {
+                return (String::new(), 0);
+            }
+            let mut text = String::new();
+            let mut length = 0;
+            for (x, value) in board.row(y).iter().enumerate() {
+                let cell = value.to_string();
+                let differs = against.get_value(Coord::new(x as i32, y as i32)) != Some(value);
+                length += cell.chars().count();
+                text += &paint(&cell, colour && differs);
+            }
+            (text, length)
+        };
+
+        let left_width = (0..self.height())
+            .map(|y| line(self, other, y).1)
+            .max()
+            .unwrap_or(0);
+        let mut text = String::new();
+        for y in 0..self.height().max(other.height()) {
+            let (left, length) = line(self, other, y);
+            let (right, _) = line(other, self, y);
+            text += &format!("{}{} | {}\n", left, " ".repeat(left_width - length), right);
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board::diff::CellChange;
+    use crate::board::{Board, Coord};
+
+    #[test]
+    fn test_diff_same_size_and_resized() {
+        let old = Board::<char>::from_string("ab\ncd\n").unwrap();
+        let new = Board::<char>::from_string("ax\ncd\n").unwrap();
+        let diff = old.diff(&new);
+        assert_eq!(diff.changed, vec![CellChange { coord: Coord::new(1, 0), old: 'b', new: 'x' }]);
+        assert!(!diff.is_empty());
+        assert!(old.diff(&old).is_empty());
+
+        let grown = Board::<char>::from_string("abz\ncdz\nzzz\n").unwrap();
+        let diff = old.diff(&grown);
+        assert!(diff.changed.is_empty());
+        assert_eq!((diff.added_rows, diff.added_columns), (2..3, 2..3));
+        let diff = grown.diff(&old);
+        assert_eq!((diff.removed_rows, diff.removed_columns), (2..3, 2..3));
+    }
+
+    #[test]
+    fn test_side_by_side() {
+        let old = Board::<char>::from_string("ab\ncd\n").unwrap();
+        let new = Board::<char>::from_string("ax\ncd\nef\n").unwrap();
+        assert_eq!(old.render_side_by_side(&new, false), "ab | ax\ncd | cd\n   | ef\n");
+        let coloured = old.render_side_by_side(&new, true);
+        assert!(coloured.starts_with("a\x1b[1;33;40mb\x1b[0m | a\x1b[1;33;40mx\x1b[0m\n"));
+        assert!(coloured.ends_with("   | \x1b[1;33;40me\x1b[0m\x1b[1;33;40mf\x1b[0m\n"));
+    }
+}
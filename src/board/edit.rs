@@ -0,0 +1,181 @@
+use crate::board::view::Rect;
+use crate::board::{Board, Coord};
+use std::fmt::{Debug, Display};
+
+// Row and column editing. Like `Vec`, these panic on out of range indices and
+// on rows or columns of the wrong length
+impl<T> Board<T>
+where
+    T: PartialEq + Debug + Display + Copy,
+{
+    // Cells of column `x`, top to bottom
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "Column {} is outside the board", x);
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    // Keeps the invariant that a board without columns has no rows and the other way round
+    fn drop_if_empty(&mut self) {
+        if self.width == 0 || self.height == 0 {
+            self.cells.clear();
+            self.width = 0;
+            self.height = 0;
+        }
+    }
+
+    pub fn insert_row(&mut self, y: usize, row: Vec<T>) {
+        assert!(y <= self.height, "Row {} is outside the board", y);
+        if self.height == 0 {
+            self.width = row.len();
+        }
+        assert_eq!(row.len(), self.width, "Row of the wrong length");
+        let at = y * self.width;
+        self.cells.splice(at..at, row);
+        self.height += 1;
+        self.drop_if_empty();
+    }
+
+    pub fn insert_column(&mut self, x: usize, column: Vec<T>) {
+        assert!(x <= self.width, "Column {} is outside the board", x);
+        if self.width == 0 {
+            self.height = column.len();
+        }
+        assert_eq!(column.len(), self.height, "Column of the wrong length");
+        let mut cells = Vec::with_capacity(self.cells.len() + column.len());
+        for (y, value) in column.into_iter().enumerate() {
+            let row = &self.cells[y * self.width..(y + 1) * self.width];
+            cells.extend_from_slice(&row[..x]);
+            cells.push(value);
+            cells.extend_from_slice(&row[x..]);
+        }
+        self.cells = cells;
+        self.width += 1;
+        self.drop_if_empty();
+    }
+
+    pub fn remove_row(&mut self, y: usize) -> Vec<T> {
+        assert!(y < self.height, "Row {} is outside the board", y);
+        let row = self.cells.drain(y * self.width..(y + 1) * self.width).collect();
+        self.height -= 1;
+        self.drop_if_empty();
+        row
+    }
+
+    pub fn remove_column(&mut self, x: usize) -> Vec<T> {
+        let column: Vec<T> = self.column(x).copied().collect();
+        let width = self.width;
+        let mut idx = 0;
+        self.cells.retain(|_| {
+            idx += 1;
+            (idx - 1) % width != x
+        });
+        self.width -= 1;
+        self.drop_if_empty();
+        column
+    }
+
+    pub fn pop_row(&mut self) -> Option<Vec<T>> {
+        (self.height > 0).then(|| self.remove_row(self.height - 1))
+    }
+
+    pub fn swap_rows(&mut self, a: usize, b: usize) {
+        assert!(a < self.height && b < self.height, "Row {} or {} is outside the board", a, b);
+        for x in 0..self.width {
+            self.cells.swap(a * self.width + x, b * self.width + x);
+        }
+    }
+
+    pub fn swap_columns(&mut self, a: usize, b: usize) {
+        assert!(a < self.width && b < self.width, "Column {} or {} is outside the board", a, b);
+        for y in 0..self.height {
+            self.cells.swap(y * self.width + a, y * self.width + b);
+        }
+    }
+
+    // Smallest rectangle holding every cell that is not empty
+    pub fn content_bounds(&self, is_empty: impl Fn(&T) -> bool) -> Option<Rect> {
+        let content: Vec<Coord> = (0..self.cells.len())
+            .filter(|&idx| !is_empty(&self.cells[idx]))
+            .map(|idx| self.coord_of(idx))
+            .collect();
+        let min = Coord::new(content.iter().map(|c| c.x()).min()?, content.iter().map(|c| c.y()).min()?);
+        let max = Coord::new(content.iter().map(|c| c.x()).max()?, content.iter().map(|c| c.y()).max()?);
+        Some(Rect::from_corners(min, max))
+    }
+
+    pub fn crop(&self, rect: Rect) -> Option<Self> {
+        self.view(rect).map(|view| view.to_board().with_wrap(self.wrap))
+    }
+
+    // Drops the empty rows and columns around the content. A board with no content
+    // becomes an empty board
+    pub fn crop_to_content(&self, is_empty: impl Fn(&T) -> bool) -> Self {
+        match self.content_bounds(is_empty) {
+            Some(rect) => self.crop(rect).unwrap(),
+            None => Self::from_parts(vec![], 0, 0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board::{Board, BoardError};
+
+    fn board(text: &str) -> Board<char> {
+        Board::<char>::from_string(text).unwrap()
+    }
+
+    #[test]
+    fn test_columns_and_edits() {
+        let mut b = board("abc\ndef\n");
+        assert_eq!(b.column(1).collect::<String>(), "be");
+        assert_eq!(b.columns().map(|c| c.collect::<String>()).collect::<Vec<_>>(), vec!["ad", "be", "cf"]);
+
+        b.insert_row(1, vec!['x', 'y', 'z']);
+        b.insert_column(3, vec!['1', '2', '3']);
+        assert_eq!(b, board("abc1\nxyz2\ndef3\n"));
+        b.swap_rows(0, 2);
+        b.swap_columns(0, 3);
+        assert_eq!(b, board("3efd\n2yzx\n1bca\n"));
+        assert_eq!(b.remove_column(1), vec!['e', 'y', 'b']);
+        assert_eq!(b.remove_row(0), vec!['3', 'f', 'd']);
+        assert_eq!(b, board("2zx\n1ca\n"));
+
+        let mut single = board("a\n");
+        single.remove_column(0);
+        assert_eq!((single.width(), single.height()), (0, 0));
+        single.insert_column(0, vec!['p', 'q']);
+        assert_eq!(single, board("p\nq\n"));
+    }
+
+    #[test]
+    fn test_ragged_input_padding_and_cropping() {
+        assert!(matches!(
+            Board::from_columns(vec![vec![1, 2], vec![3]]),
+            Err(BoardError::RaggedColumn { column: 1, expected: 2, found: 1 })
+        ));
+        let padded = Board::from_ragged_rows(vec![vec!['a'], vec![], vec!['b', 'c']], '.');
+        assert_eq!(padded, board("a.\n..\nbc\n"));
+
+        let sparse = board(".....\n..#..\n...#.\n.....\n");
+        assert_eq!(sparse.crop_to_content(|&c| c == '.'), board("#.\n.#\n"));
+        assert_eq!(board("...\n").crop_to_content(|&c| c == '.').height(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Column 0 or 3 is outside the board")]
+    fn test_swap_columns_outside() {
+        // Column 3 of a 3x2 board would be cell 0 of the next row
+        board("abc\ndef\n").swap_columns(0, 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Row 2 or 0 is outside the board")]
+    fn test_swap_rows_outside() {
+        board("abc\ndef\n").swap_rows(2, 0);
+    }
+}
@@ -0,0 +1,177 @@
+use crate::board::{Board, Coord};
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::fmt::{Debug, Display};
+use std::fs;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+// Drawn on top of the cells: a path through cell centres and a set of marked cells
+#[derive(Clone, Debug)]
+pub struct Overlay {
+    pub path: Vec<Coord>,
+    pub highlight: HashSet<Coord>,
+    pub path_colour: Rgb,
+    pub highlight_colour: Rgb,
+}
+
+impl Default for Overlay {
+    fn default() -> Self {
+        Self {
+            path: vec![],
+            highlight: HashSet::new(),
+            path_colour: Rgb(220, 30, 30),
+            highlight_colour: Rgb(250, 200, 0),
+        }
+    }
+}
+
+// Each cell becomes a `scale` x `scale` square of pixels
+pub struct Palette<'a, T> {
+    pub colour: &'a dyn Fn(&T) -> Rgb,
+    pub scale: usize,
+    pub overlay: Overlay,
+}
+
+pub struct SvgStyle<'a, T> {
+    pub colour: &'a dyn Fn(&T) -> Rgb,
+    pub cell_size: usize,
+    pub grid_lines: bool,
+    pub overlay: Overlay,
+}
+
+impl<T> Board<T>
+where
+    T: PartialEq + Debug + Display + Copy,
+{
+    // Binary PPM (P6). Highlighted cells take the highlight colour and path cells get a
+    // square of the path colour in the middle
+    pub fn ppm_bytes(&self, palette: &Palette<T>) -> Vec<u8> {
+        let scale = palette.scale.max(1);
+        let (width, height) = (self.width() * scale, self.height() * scale);
+        let path: HashSet<Coord> = palette.overlay.path.iter().copied().collect();
+        let inner = scale / 4..scale - scale / 4;
+
+        let mut bytes = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+        for py in 0..height {
+            for px in 0..width {
+                let coord = Coord::new((px / scale) as i32, (py / scale) as i32);
+                let on_path = path.contains(&coord)
+                    && inner.contains(&(px % scale))
+                    && inner.contains(&(py % scale));
+                let Rgb(r, g, b) = if on_path {
+                    palette.overlay.path_colour
+                } else if palette.overlay.highlight.contains(&coord) {
+                    palette.overlay.highlight_colour
+                } else {
+                    (palette.colour)(self.get_value(coord).unwrap())
+                };
+                bytes.extend([r, g, b]);
+            }
+        }
+        bytes
+    }
+
+    pub fn to_ppm(&self, path: &str, palette: &Palette<T>) -> Result<()> {
+        fs::write(path, self.ppm_bytes(palette)).with_context(|| format!("Can not write {}", path))
+    }
+
+    // One square per cell, highlighted cells outlined and the path as a polyline
+    pub fn svg_string(&self, style: &SvgStyle<T>) -> String {
+        let size = style.cell_size.max(1);
+        let stroke = if style.grid_lines { " stroke=\"#808080\" stroke-width=\"0.5\"" } else { "" };
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\">\n",
+            self.width() * size,
+            self.height() * size
+        );
+        for (idx, value) in self.cells().iter().enumerate() {
+            let coord = self.coord_of(idx);
+            svg += &format!(
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"{}/>\n",
+                coord.x() as usize * size,
+                coord.y() as usize * size,
+                size,
+                size,
+                (style.colour)(value).hex(),
+                stroke
+            );
+        }
+
+        let overlay = &style.overlay;
+        let mut highlight: Vec<&Coord> = overlay.highlight.iter().collect();
+        highlight.sort_by_key(|c| (c.y(), c.x()));
+        for coord in highlight {
+            svg += &format!(
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"2\"/>\n",
+                coord.x() as usize * size,
+                coord.y() as usize * size,
+                size,
+                size,
+                overlay.highlight_colour.hex()
+            );
+        }
+        if !overlay.path.is_empty() {
+            let centre = |v: i32| v as f64 * size as f64 + size as f64 / 2.0;
+            let points: Vec<String> = overlay
+                .path
+                .iter()
+                .map(|c| format!("{},{}", centre(c.x()), centre(c.y())))
+                .collect();
+            svg += &format!(
+                "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\"/>\n",
+                points.join(" "),
+                overlay.path_colour.hex(),
+                (size as f64 / 4.0).max(1.0)
+            );
+        }
+        svg += "</svg>\n";
+        svg
+    }
+
+    pub fn to_svg(&self, path: &str, style: &SvgStyle<T>) -> Result<()> {
+        fs::write(path, self.svg_string(style)).with_context(|| format!("Can not write {}", path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board::image::{Overlay, Palette, Rgb, SvgStyle};
+    use crate::board::{Board, Coord};
+
+    fn colour(c: &char) -> Rgb {
+        if *c == '#' { Rgb(0, 0, 0) } else { Rgb(255, 255, 255) }
+    }
+
+    #[test]
+    fn test_ppm_pixels() {
+        let board = Board::<char>::from_string("#.\n..\n").unwrap();
+        let overlay = Overlay { highlight: [Coord::new(1, 1)].into(), ..Overlay::default() };
+        let bytes = board.ppm_bytes(&Palette { colour: &colour, scale: 2, overlay });
+        let header = b"P6\n4 4\n255\n";
+        assert_eq!(&bytes[..header.len()], header);
+        let pixels = &bytes[header.len()..];
+        assert_eq!(pixels.len(), 4 * 4 * 3);
+        assert_eq!(&pixels[0..3], &[0, 0, 0]);
+        assert_eq!(&pixels[6..9], &[255, 255, 255]);
+        assert_eq!(&pixels[pixels.len() - 3..], &[250, 200, 0]);
+    }
+
+    #[test]
+    fn test_svg_overlay() {
+        let board = Board::<char>::from_string("#..\n...\n").unwrap();
+        let overlay = Overlay { path: vec![Coord::new(0, 0), Coord::new(1, 0), Coord::new(1, 1)], ..Overlay::default() };
+        let svg = board.svg_string(&SvgStyle { colour: &colour, cell_size: 10, grid_lines: true, overlay });
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"30\" height=\"20\">"));
+        assert_eq!(svg.matches("<rect").count(), 6);
+        assert!(svg.contains("fill=\"#000000\""));
+        assert!(svg.contains("points=\"5,5 15,5 15,15\""));
+    }
+}
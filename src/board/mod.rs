@@ -0,0 +1,646 @@
+pub mod automaton;
+pub mod bits;
+pub mod cycle;
+pub mod diff;
+pub mod edit;
+pub mod image;
+pub mod path;
+pub mod record;
+pub mod region;
+pub mod render;
+pub mod sparse;
+pub mod transform;
+pub mod view;
+
+use crate::point::Point;
+use anyhow::{anyhow, Context, Result};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::ops::Add;
+
+// How rows of different lengths are handled when loading a board
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RowShape {
+    Rectangular,
+    // Short rows are padded to the longest one with this character before parsing
+    PadWith(char),
+}
+
+#[derive(Debug)]
+pub enum BoardError {
+    InvalidCell {
+        coord: Coord,
+        value: char,
+        reason: anyhow::Error,
+    },
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    RaggedColumn {
+        column: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl Display for BoardError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BoardError::InvalidCell { coord, value, reason } => write!(
+                f,
+                "Invalid cell {:?} at ({}, {}): {}",
+                value, coord.x(), coord.y(), reason
+            ),
+            BoardError::RaggedRow { row, expected, found } => write!(
+                f,
+                "Row {} has {} cells, expected {}",
+                row, found, expected
+            ),
+            BoardError::RaggedColumn { column, expected, found } => write!(
+                f,
+                "Column {} has {} cells, expected {}",
+                column, found, expected
+            ),
+        }
+    }
+}
+
+impl Error for BoardError {}
+
+// Which axes wrap around, turning the board into a cylinder or a torus
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Wrap {
+    pub x: bool,
+    pub y: bool,
+}
+
+impl Wrap {
+    pub const NONE: Wrap = Wrap { x: false, y: false };
+    pub const BOTH: Wrap = Wrap { x: true, y: true };
+
+    // Same wrapping once x and y swap places
+    pub fn swapped(self) -> Wrap {
+        Wrap { x: self.y, y: self.x }
+    }
+}
+
+// Coordinates of each marker character found while parsing
+pub type Markers = HashMap<char, Vec<Coord>>;
+
+// Cells are stored row after row in a single vector
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Board<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+    wrap: Wrap,
+}
+
+// Position along an axis of `size` cells, if it is on the board
+fn axis_index(value: i32, size: usize, wrap: bool) -> Option<usize> {
+    if wrap && size > 0 {
+        return Some(value.rem_euclid(size as i32) as usize);
+    }
+    let value: usize = value.try_into().ok()?;
+    (value < size).then_some(value)
+}
+
+impl<T> Board<T>
+where
+    T: PartialEq + Debug + Display + Copy,
+{
+    // Panics if the rows do not all have the same length
+    pub fn new(rows: Vec<Vec<T>>) -> Self {
+        Self::from_rows(rows).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, BoardError> {
+        let width = rows.first().map_or(0, |row| row.len());
+        if let Some((row, found)) = rows.iter().enumerate().find(|(_, row)| row.len() != width) {
+            return Err(BoardError::RaggedRow { row, expected: width, found: found.len() });
+        }
+        let height = if width == 0 { 0 } else { rows.len() };
+        Ok(Self::from_parts(rows.concat(), width, height))
+    }
+
+    pub fn from_columns(cols: Vec<Vec<T>>) -> Result<Self, BoardError> {
+        match Self::from_rows(cols) {
+            Ok(board) => Ok(board.transpose()),
+            Err(BoardError::RaggedRow { row, expected, found }) => {
+                Err(BoardError::RaggedColumn { column: row, expected, found })
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    // Short rows are filled up to the longest one with `fill`
+    pub fn from_ragged_rows(mut rows: Vec<Vec<T>>, fill: T) -> Self {
+        let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        for row in &mut rows {
+            row.resize(width, fill);
+        }
+        Self::new(rows)
+    }
+
+    // Reads one row per line, ignoring trailing empty lines
+    pub fn from_reader(
+        reader: impl BufRead,
+        parser: &dyn Fn(char) -> Result<T>,
+        shape: RowShape,
+    ) -> Result<Self> {
+        let mut lines: Vec<String> = reader.lines().collect::<Result<_, _>>()?;
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+        let width = match shape {
+            RowShape::Rectangular => lines.first().map_or(0, |line| line.chars().count()),
+            RowShape::PadWith(_) => lines.iter().map(|line| line.chars().count()).max().unwrap_or(0),
+        };
+        if lines.is_empty() {
+            return Ok(Self::from_parts(vec![], 0, 0));
+        }
+
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for (y, line) in lines.iter().enumerate() {
+            let mut found = 0;
+            for (x, c) in line.chars().enumerate() {
+                let value = parser(c).map_err(|reason| BoardError::InvalidCell {
+                    coord: Coord::new(x as i32, y as i32),
+                    value: c,
+                    reason,
+                })?;
+                cells.push(value);
+                found += 1;
+            }
+            match shape {
+                RowShape::Rectangular if found != width => {
+                    Err(BoardError::RaggedRow { row: y, expected: width, found })?
+                }
+                RowShape::PadWith(fill) => {
+                    for x in found..width {
+                        let value = parser(fill).map_err(|reason| BoardError::InvalidCell {
+                            coord: Coord::new(x as i32, y as i32),
+                            value: fill,
+                            reason,
+                        })?;
+                        cells.push(value);
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(Self::from_parts(cells, width, lines.len()))
+    }
+
+    pub fn parse(text: &str, parser: &dyn Fn(char) -> Result<T>, shape: RowShape) -> Result<Self> {
+        Self::from_reader(text.as_bytes(), parser, shape)
+    }
+
+    // Like `parse`, but cells holding one of `markers` become `background` and their
+    // coordinates are returned by marker, in reading order. Every marker has an entry
+    pub fn parse_with_markers(
+        text: &str,
+        parser: &dyn Fn(char) -> Result<T>,
+        markers: &[char],
+        background: T,
+    ) -> Result<(Self, Markers)> {
+        let board = Self::parse(
+            text,
+            &|c| if markers.contains(&c) { Ok(background) } else { parser(c) },
+            RowShape::Rectangular,
+        )?;
+        let mut found: Markers = markers.iter().map(|&m| (m, vec![])).collect();
+        for (y, line) in text.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                if let Some(coords) = found.get_mut(&c) {
+                    coords.push(Coord::new(x as i32, y as i32));
+                }
+            }
+        }
+        Ok((board, found))
+    }
+
+    pub fn read_board(path: &str, parser: &dyn Fn(char) -> Result<T>, shape: RowShape) -> Result<Self> {
+        let file = File::open(path).with_context(|| format!("Can not open {}", path))?;
+        Self::from_reader(BufReader::new(file), parser, shape)
+    }
+
+    pub fn filled(width: usize, height: usize, default_value: T) -> Self {
+        if width == 0 || height == 0 {
+            return Self::from_parts(vec![], 0, 0);
+        }
+        Self::from_parts(vec![default_value; width * height], width, height)
+    }
+
+    fn from_parts(cells: Vec<T>, width: usize, height: usize) -> Self {
+        Self { cells, width, height, wrap: Wrap::NONE }
+    }
+
+    pub fn wrap(&self) -> Wrap {
+        self.wrap
+    }
+
+    // Coordinates off a wrapping axis come back in on the other side, for every accessor
+    pub fn with_wrap(mut self, wrap: Wrap) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    // Position of `coord` in `cells`, if it is on the board
+    pub fn index(&self, coord: Coord) -> Option<usize> {
+        let x = axis_index(coord.x(), self.width, self.wrap.x)?;
+        let y = axis_index(coord.y(), self.height, self.wrap.y)?;
+        Some(y * self.width + x)
+    }
+
+    // Inverse of `index`
+    pub fn coord_of(&self, index: usize) -> Coord {
+        Coord::new((index % self.width) as i32, (index / self.width) as i32)
+    }
+
+    pub fn get_value(&self, coord: Coord) -> Option<&T> {
+        self.index(coord).map(|idx| &self.cells[idx])
+    }
+
+    pub fn set_value(&mut self, coord: Coord, value: T) {
+        let idx = self
+            .index(coord)
+            .unwrap_or_else(|| panic!("({}, {}) is outside the board", coord.x(), coord.y()));
+        self.cells[idx] = value;
+    }
+
+    pub fn is_in_bound(&self, coord: Coord) -> bool {
+        self.index(coord).is_some()
+    }
+
+    // All cells in reading order
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
+        self.cells.chunks_mut(self.width.max(1))
+    }
+
+    // In-bounds cells at the given offsets from `coord`. On a wrapping axis the
+    // coordinates are brought back onto the board
+    pub fn neighbors<'a>(&'a self, coord: Coord, stencil: &'a [Coord]) -> impl Iterator<Item = (Coord, &'a T)> {
+        coord
+            .stencil(stencil)
+            .filter_map(move |adj| self.index(adj).map(|idx| (self.coord_of(idx), &self.cells[idx])))
+    }
+
+    pub fn neighbors4(&self, coord: Coord) -> impl Iterator<Item = (Coord, &T)> {
+        self.neighbors(coord, &NEIGHBORS4)
+    }
+
+    pub fn neighbors8(&self, coord: Coord) -> impl Iterator<Item = (Coord, &T)> {
+        self.neighbors(coord, &NEIGHBORS8)
+    }
+
+    pub fn find_element(&self, element: T) -> Option<Coord> {
+        self.cells
+            .iter()
+            .position(|cell| cell == &element)
+            .map(|idx| self.coord_of(idx))
+    }
+
+    pub fn print_board(&self) {
+        print!("{}", self);
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut cells = Vec::with_capacity(self.cells.len());
+        for x in 0..self.width {
+            cells.extend((0..self.height).map(|y| self.cells[y * self.width + x]));
+        }
+        Self::from_parts(cells, self.height, self.width).with_wrap(self.wrap.swapped())
+    }
+}
+
+impl Board<char> {
+    pub fn read_char_board(path: &str) -> Result<Self> {
+        Self::read_board(path, &|c| Ok(c), RowShape::Rectangular)
+    }
+
+    pub fn from_string(p0: &str) -> Result<Self> {
+        Self::parse(p0, &|c| Ok(c), RowShape::Rectangular)
+    }
+
+    pub fn empty(width: usize, height: usize) -> Self {
+        Self::filled(width, height, '.')
+    }
+
+    pub fn count_values(&self, c: char) -> usize {
+        self.cells.iter()
+            .filter(|&&val| val == c)
+            .count()
+    }
+}
+
+fn parse_digit(c: char) -> Result<i32> {
+    c.to_digit(10)
+        .map(|d| d as i32)
+        .ok_or_else(|| anyhow!("not a digit"))
+}
+
+impl Board<i32> {
+    pub fn read_int_board(path: &str) -> Result<Self> {
+        Self::read_board(path, &parse_digit, RowShape::Rectangular)
+    }
+
+    pub fn from_string(p0: &str) -> Result<Self> {
+        Self::parse(p0, &parse_digit, RowShape::Rectangular)
+    }
+
+    pub fn empty(width: usize, height: usize) -> Self {
+        Self::filled(width, height, 0)
+    }
+}
+
+// Cell position, x to the right and y downwards
+pub type Coord = Point<i32, 2>;
+
+// Offsets of the orthogonal neighbours, clockwise from up
+pub const NEIGHBORS4: [Coord; 4] = [
+    Direction::Up.delta(),
+    Direction::Right.delta(),
+    Direction::Down.delta(),
+    Direction::Left.delta(),
+];
+
+// Offsets of the orthogonal and diagonal neighbours, clockwise from up
+pub const NEIGHBORS8: [Coord; 8] = [
+    Direction::Up.delta(),
+    Direction::UpRight.delta(),
+    Direction::Right.delta(),
+    Direction::DownRight.delta(),
+    Direction::Down.delta(),
+    Direction::DownLeft.delta(),
+    Direction::Left.delta(),
+    Direction::UpLeft.delta(),
+];
+
+// Compass directions with y growing downwards, in clockwise order
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    pub const CARDINAL: [Direction; 4] = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    pub const fn delta(self) -> Coord {
+        match self {
+            Direction::Up => Coord::new(0, -1),
+            Direction::UpRight => Coord::new(1, -1),
+            Direction::Right => Coord::new(1, 0),
+            Direction::DownRight => Coord::new(1, 1),
+            Direction::Down => Coord::new(0, 1),
+            Direction::DownLeft => Coord::new(-1, 1),
+            Direction::Left => Coord::new(-1, 0),
+            Direction::UpLeft => Coord::new(-1, -1),
+        }
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        !Direction::CARDINAL.contains(&self)
+    }
+
+    // Clockwise by `eighths` of a full turn
+    fn rotated(self, eighths: usize) -> Direction {
+        Direction::ALL[(self as usize + eighths) % 8]
+    }
+
+    pub fn turn_right(self) -> Direction {
+        self.rotated(2)
+    }
+
+    pub fn turn_left(self) -> Direction {
+        self.rotated(6)
+    }
+
+    pub fn turn_right_45(self) -> Direction {
+        self.rotated(1)
+    }
+
+    pub fn turn_left_45(self) -> Direction {
+        self.rotated(7)
+    }
+
+    pub fn reverse(self) -> Direction {
+        self.rotated(4)
+    }
+}
+
+impl Coord {
+    // `self` moved by each offset in turn
+    pub fn stencil(self, offsets: &[Coord]) -> impl Iterator<Item = Coord> + '_ {
+        offsets.iter().map(move |&offset| self + offset)
+    }
+
+    pub fn neighbors4(self) -> impl Iterator<Item = Coord> {
+        self.stencil(&NEIGHBORS4)
+    }
+
+    pub fn neighbors8(self) -> impl Iterator<Item = Coord> {
+        self.stencil(&NEIGHBORS8)
+    }
+
+    pub fn up(&self) -> Coord {
+        Coord::new(self.x(), self.y() - 1)
+    }
+
+    pub fn down(&self) -> Coord {
+        Coord::new(self.x(), self.y() + 1)
+    }
+
+    pub fn left(&self) -> Coord {
+        Coord::new(self.x() - 1, self.y())
+    }
+
+    pub fn right(&self) -> Coord {
+        Coord::new(self.x() + 1, self.y())
+    }
+
+    // Quarter turn about `origin`, clockwise as drawn with y growing downwards
+    pub fn rotate_cw(&self, origin: Coord) -> Coord {
+        let d = *self - origin;
+        origin + Coord::new(-d.y(), d.x())
+    }
+
+    pub fn rotate_ccw(&self, origin: Coord) -> Coord {
+        let d = *self - origin;
+        origin + Coord::new(d.y(), -d.x())
+    }
+}
+
+impl Add<Direction> for Coord {
+    type Output = Self;
+
+    fn add(self, rhs: Direction) -> Self::Output {
+        self + rhs.delta()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::board::automaton::Automaton;
+    use crate::board::{Board, BoardError, Coord, Direction, RowShape, Wrap, NEIGHBORS4};
+    use anyhow::{anyhow, bail};
+
+    #[test]
+    fn test_bad_cell_reports_coordinate() {
+        let err = Board::<i32>::from_string("123\n4x6\n").unwrap_err();
+        match err.downcast_ref::<BoardError>() {
+            Some(BoardError::InvalidCell { coord, value, .. }) => {
+                assert_eq!(*coord, Coord::new(1, 1));
+                assert_eq!(*value, 'x');
+            }
+            other => panic!("Unexpected error {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_ragged_rows() {
+        let text = "..#\n.\n\n";
+        assert!(matches!(
+            Board::<char>::from_string(text).unwrap_err().downcast_ref::<BoardError>(),
+            Some(BoardError::RaggedRow { row: 1, expected: 3, found: 1 })
+        ));
+        let board = Board::parse(text, &|c| Ok(c), RowShape::PadWith(' ')).unwrap();
+        assert_eq!(board.rows().collect::<Vec<_>>(), vec![&['.', '.', '#'], &['.', ' ', ' ']]);
+
+        let parser = |c| match c {
+            'a' => Ok(true),
+            'b' => Ok(false),
+            _ => bail!("expected 'a' or 'b'"),
+        };
+        let custom = Board::parse("ab\nba", &parser, RowShape::Rectangular).unwrap();
+        assert_eq!(custom.cells(), &[true, false, false, true]);
+    }
+
+    #[test]
+    fn test_flat_rows_and_transpose() {
+        let mut board = Board::<i32>::from_string("123\n456\n").unwrap();
+        assert_eq!((board.width(), board.height()), (3, 2));
+        assert_eq!(board.row(1), &[4, 5, 6]);
+        assert_eq!(board.get_value(Coord::new(3, 0)), None);
+        assert_eq!(board.find_element(5), Some(Coord::new(1, 1)));
+
+        let transposed = board.transpose();
+        assert_eq!(transposed.rows().collect::<Vec<_>>(), vec![&[1, 4], &[2, 5], &[3, 6]]);
+        assert_eq!(Board::from_columns(vec![vec![1, 4], vec![2, 5], vec![3, 6]]).unwrap().cells(), board.cells());
+
+        assert_eq!(board.pop_row(), Some(vec![4, 5, 6]));
+        assert_eq!(board.height(), 1);
+        assert!(matches!(
+            Board::from_rows(vec![vec![1, 2], vec![3]]),
+            Err(BoardError::RaggedRow { row: 1, expected: 2, found: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_neighbors_stay_in_bounds() {
+        let board = Board::<i32>::from_string("123\n456\n").unwrap();
+        let corner: Vec<(Coord, &i32)> = board.neighbors8(Coord::new(0, 0)).collect();
+        assert_eq!(corner, vec![(Coord::new(1, 0), &2), (Coord::new(1, 1), &5), (Coord::new(0, 1), &4)]);
+        assert_eq!(board.neighbors4(Coord::new(1, 1)).map(|(_, &v)| v).sum::<i32>(), 2 + 6 + 4);
+        assert_eq!(Coord::new(5, 5).neighbors8().count(), 8);
+
+        let knight = [Coord::new(1, 2), Coord::new(2, 1), Coord::new(-1, -2)];
+        assert_eq!(board.neighbors(Coord::new(0, 0), &knight).count(), 1);
+        assert_eq!(Coord::new(0, 0).stencil(&NEIGHBORS4).next(), Some(Coord::new(0, -1)));
+    }
+
+    #[test]
+    fn test_directions_and_coord_algebra() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left_45(), Direction::UpLeft);
+        assert_eq!(Direction::DownLeft.reverse(), Direction::UpRight);
+        assert!(Direction::ALL.iter().all(|&d| d.turn_left().turn_right() == d));
+        assert!(Direction::ALL.iter().all(|&d| d.reverse().delta() == -d.delta()));
+
+        let c = Coord::new(3, 1);
+        assert_eq!(c + Direction::Down, c.down());
+        assert_eq!(c * 2, Coord::new(6, 2));
+        assert_eq!(c.manhattan(Coord::new(0, 5)), 7);
+        assert_eq!(c.chebyshev(Coord::new(0, 5)), 4);
+
+        let origin = Coord::new(1, 1);
+        assert_eq!((origin + Direction::Up.delta() * 2).rotate_cw(origin), origin + Direction::Right.delta() * 2);
+        assert_eq!(c.rotate_cw(origin).rotate_ccw(origin), c);
+    }
+
+    #[test]
+    fn test_wrapping_axes() {
+        let board = Board::<i32>::from_string("123\n456\n").unwrap().with_wrap(Wrap { x: true, y: false });
+        assert_eq!(board.get_value(Coord::new(-1, 0)), Some(&3));
+        assert_eq!(board.get_value(Coord::new(7, 1)), Some(&5));
+        assert_eq!(board.get_value(Coord::new(0, 2)), None);
+        let left: Vec<_> = board.neighbors4(Coord::new(0, 0)).collect();
+        assert_eq!(left, vec![(Coord::new(1, 0), &2), (Coord::new(0, 1), &4), (Coord::new(2, 0), &3)]);
+        assert_eq!(board.transpose().wrap(), Wrap { x: false, y: true });
+
+        // A cell spreading on a torus reaches the far corner in one step
+        let mut torus = Board::<char>::from_string("#...\n....\n....\n").unwrap().with_wrap(Wrap::BOTH);
+        Automaton::new(|c, n| if n.count('#') > 0 { '#' } else { *c }).step(&mut torus);
+        assert_eq!(torus.get_value(Coord::new(3, 2)), Some(&'#'));
+    }
+
+    #[test]
+    fn test_parse_with_markers() {
+        let text = "S.E\n.S.\n";
+        let (board, markers) = Board::parse_with_markers(text, &|c| Ok(c), &['S', 'E', 'X'], '.').unwrap();
+        assert_eq!(board, Board::<char>::from_string("...\n...\n").unwrap());
+        assert_eq!(markers[&'S'], vec![Coord::new(0, 0), Coord::new(1, 1)]);
+        assert_eq!(markers[&'E'], vec![Coord::new(2, 0)]);
+        assert!(markers[&'X'].is_empty());
+
+        let digits = Board::parse_with_markers("1a\n23\n", &|c| c.to_digit(10).ok_or_else(|| anyhow!("not a digit")), &['a'], 0);
+        assert_eq!(digits.unwrap().0.cells(), &[1, 0, 2, 3]);
+    }
+}
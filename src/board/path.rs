@@ -0,0 +1,174 @@
+use crate::board::{Board, Coord};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+use std::fmt::{Debug, Display};
+
+// Length of a shortest path and the cells along it, both ends included
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Path {
+    pub distance: u64,
+    pub coords: Vec<Coord>,
+}
+
+// Walks the predecessors back from `goal`, indexed like the board cells
+fn rebuild<T>(board: &Board<T>, previous: &[Option<usize>], goal: usize, distance: u64) -> Path
+where
+    T: PartialEq + Debug + Display + Copy,
+{
+    let mut coords = vec![board.coord_of(goal)];
+    let mut current = goal;
+    while let Some(idx) = previous[current] {
+        coords.push(board.coord_of(idx));
+        current = idx;
+    }
+    coords.reverse();
+    Path { distance, coords }
+}
+
+// Fewest orthogonal steps from `start` to `goal` through cells accepted by `passable`
+pub fn bfs<T>(board: &Board<T>, start: Coord, goal: Coord, passable: impl Fn(Coord, &T) -> bool) -> Option<Path>
+where
+    T: PartialEq + Debug + Display + Copy,
+{
+    let start_idx = board.index(start)?;
+    let goal_idx = board.index(goal)?;
+    let mut distance: Vec<Option<u64>> = vec![None; board.cells().len()];
+    let mut previous: Vec<Option<usize>> = vec![None; board.cells().len()];
+    let mut queue = VecDeque::from([start]);
+    distance[start_idx] = Some(0);
+
+    while let Some(coord) = queue.pop_front() {
+        let idx = board.index(coord)?;
+        let steps = distance[idx]?;
+        if idx == goal_idx {
+            return Some(rebuild(board, &previous, idx, steps));
+        }
+        for (next, value) in board.neighbors4(coord) {
+            let next_idx = board.index(next)?;
+            if distance[next_idx].is_none() && passable(next, value) {
+                distance[next_idx] = Some(steps + 1);
+                previous[next_idx] = Some(idx);
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+// `cost` is the price of stepping onto a cell, or None if it can not be entered
+fn search<T>(
+    board: &Board<T>,
+    start: Coord,
+    goal: Coord,
+    cost: impl Fn(Coord, &T) -> Option<u64>,
+    heuristic: impl Fn(Coord) -> u64,
+) -> Option<Path>
+where
+    T: PartialEq + Debug + Display + Copy,
+{
+    let start_idx = board.index(start)?;
+    let goal_idx = board.index(goal)?;
+    let mut distance: Vec<Option<u64>> = vec![None; board.cells().len()];
+    let mut previous: Vec<Option<usize>> = vec![None; board.cells().len()];
+    let mut queue = BinaryHeap::from([Reverse((heuristic(start), 0, start_idx))]);
+    distance[start_idx] = Some(0);
+
+    while let Some(Reverse((_, spent, idx))) = queue.pop() {
+        if idx == goal_idx {
+            return Some(rebuild(board, &previous, idx, spent));
+        }
+        if distance[idx].is_some_and(|best| best < spent) {
+            continue;
+        }
+        for (next, value) in board.neighbors4(board.coord_of(idx)) {
+            let Some(step) = cost(next, value) else {
+                continue;
+            };
+            let next_idx = board.index(next)?;
+            let total = spent + step;
+            if distance[next_idx].is_none_or(|best| total < best) {
+                distance[next_idx] = Some(total);
+                previous[next_idx] = Some(idx);
+                queue.push(Reverse((total + heuristic(next), total, next_idx)));
+            }
+        }
+    }
+    None
+}
+
+// Cheapest orthogonal path, paying `cost` for every cell entered after `start`
+pub fn dijkstra<T>(board: &Board<T>, start: Coord, goal: Coord, cost: impl Fn(Coord, &T) -> Option<u64>) -> Option<Path>
+where
+    T: PartialEq + Debug + Display + Copy,
+{
+    search(board, start, goal, cost, |_| 0)
+}
+
+// Manhattan distance, going round the back of wrapping axes when that is shorter
+fn wrapped_manhattan<T>(board: &Board<T>, a: Coord, b: Coord) -> u64
+where
+    T: PartialEq + Debug + Display + Copy,
+{
+    let axis = |d: i32, size: usize, wrap: bool| {
+        let d = d.unsigned_abs() as u64;
+        if wrap { d.min((size as u64).saturating_sub(d)) } else { d }
+    };
+    axis(a.x() - b.x(), board.width(), board.wrap().x) + axis(a.y() - b.y(), board.height(), board.wrap().y)
+}
+
+// Same result as `dijkstra`, guided towards `goal`. Every step must cost at least 1
+pub fn astar<T>(board: &Board<T>, start: Coord, goal: Coord, cost: impl Fn(Coord, &T) -> Option<u64>) -> Option<Path>
+where
+    T: PartialEq + Debug + Display + Copy,
+{
+    search(board, start, goal, cost, |coord| wrapped_manhattan(board, coord, goal))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board::path::{astar, bfs, dijkstra};
+    use crate::board::{Board, Coord, Wrap};
+
+    #[test]
+    fn test_bfs_goes_around_walls() {
+        let board = Board::<char>::from_string("..#.\n#.#.\n....\n").unwrap();
+        let path = bfs(&board, Coord::new(0, 0), Coord::new(3, 0), |_, &c| c != '#').unwrap();
+        assert_eq!(path.distance, 7);
+        assert_eq!(path.coords.len(), 8);
+        assert_eq!(path.coords[0], Coord::new(0, 0));
+        assert!(path.coords.windows(2).all(|w| w[0].manhattan(w[1]) == 1));
+
+        let walled = Board::<char>::from_string(".#.\n").unwrap();
+        assert_eq!(bfs(&walled, Coord::new(0, 0), Coord::new(2, 0), |_, &c| c != '#'), None);
+    }
+
+    #[test]
+    fn test_weighted_paths_agree() {
+        let board = Board::<i32>::from_string("1163751742\n1381373672\n2136511328\n3694931569\n7463417111\n1319128137\n1359912421\n3125421639\n1293138521\n2311944581\n").unwrap();
+        let (start, goal) = (Coord::new(0, 0), Coord::new(9, 9));
+        let cost = |_, &risk: &i32| Some(risk as u64);
+        let cheapest = dijkstra(&board, start, goal, cost).unwrap();
+        assert_eq!(cheapest.distance, 40);
+        let entered: i32 = cheapest.coords[1..].iter().map(|&c| *board.get_value(c).unwrap()).sum();
+        assert_eq!(entered, 40);
+        assert_eq!(astar(&board, start, goal, cost).unwrap().distance, 40);
+    }
+
+    fn board_contains(board: &Board<char>, c: Coord) -> bool {
+        c.x() >= 0 && c.y() >= 0 && (c.x() as usize) < board.width() && (c.y() as usize) < board.height()
+    }
+
+    #[test]
+    fn test_paths_cross_wrapping_edges() {
+        let board = Board::<char>::from_string(".#...\n.#...\n").unwrap();
+        let (start, goal) = (Coord::new(0, 0), Coord::new(4, 1));
+        assert_eq!(bfs(&board, start, goal, |_, &c| c != '#'), None);
+
+        let torus = board.with_wrap(Wrap { x: true, y: false });
+        let path = bfs(&torus, start, goal, |_, &c| c != '#').unwrap();
+        assert_eq!(path.distance, 2);
+        assert!(path.coords.iter().all(|&c| board_contains(&torus, c)));
+        let cost = |_, &c: &char| (c != '#').then_some(1);
+        assert_eq!(astar(&torus, start, goal, cost).unwrap().distance, 2);
+    }
+}
@@ -0,0 +1,207 @@
+use crate::board::image::Palette;
+use crate::board::render::Renderer;
+use crate::board::{Board, Coord};
+use anyhow::{Context, Result};
+use std::fmt::{Debug, Display};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+// Whether every step keeps a full copy of the board or only the cells that changed
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RecordMode {
+    Snapshot,
+    Diff,
+}
+
+#[derive(Clone, Debug)]
+enum Frame<T> {
+    Snapshot(Board<T>),
+    Diff(Vec<(Coord, T)>),
+}
+
+// What happened to the board between two frames
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Change<T> {
+    Resized,
+    Cells(Vec<(Coord, T)>),
+}
+
+// Cells are only compared when both boards have the same size
+fn changed_cells<T>(before: &Board<T>, after: &Board<T>) -> Change<T>
+where
+    T: PartialEq + Debug + Display + Copy,
+{
+    if before.width() != after.width() || before.height() != after.height() {
+        return Change::Resized;
+    }
+    let cells = before
+        .cells()
+        .iter()
+        .zip(after.cells())
+        .enumerate()
+        .filter(|(_, (old, new))| old != new)
+        .map(|(idx, (_, &new))| (after.coord_of(idx), new))
+        .collect();
+    Change::Cells(cells)
+}
+
+// Captures the state of a board after each step of a simulation
+#[derive(Clone, Debug)]
+pub struct Recorder<T> {
+    mode: RecordMode,
+    frames: Vec<Frame<T>>,
+    last: Option<Board<T>>,
+}
+
+impl<T> Recorder<T>
+where
+    T: PartialEq + Debug + Display + Copy,
+{
+    pub fn new(mode: RecordMode) -> Self {
+        Self { mode, frames: vec![], last: None }
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    // The first frame, and any frame where the board changes size, is always a snapshot
+    pub fn record(&mut self, board: &Board<T>) {
+        let changed = self.last.as_ref().map(|last| changed_cells(last, board));
+        let frame = match changed {
+            Some(Change::Cells(changed)) if self.mode == RecordMode::Diff => Frame::Diff(changed),
+            _ => Frame::Snapshot(board.clone()),
+        };
+        self.frames.push(frame);
+        self.last = Some(board.clone());
+    }
+
+    // Every recorded board in order, with the cells changed since the previous one
+    pub fn frames(&self) -> impl Iterator<Item = (Board<T>, Vec<Coord>)> + '_ {
+        let mut current: Option<Board<T>> = None;
+        self.frames.iter().map(move |frame| {
+            let changed = match (frame, current.as_mut()) {
+                (Frame::Diff(cells), Some(board)) => {
+                    for &(coord, value) in cells {
+                        board.set_value(coord, value);
+                    }
+                    cells.iter().map(|&(coord, _)| coord).collect()
+                }
+                (Frame::Snapshot(board), previous) => {
+                    let changed = match previous.map(|previous| changed_cells(previous, board)) {
+                        Some(Change::Cells(cells)) => cells.into_iter().map(|(coord, _)| coord).collect(),
+                        Some(Change::Resized) | None => vec![],
+                    };
+                    current = Some(board.clone());
+                    changed
+                }
+                (Frame::Diff(_), None) => unreachable!("The first frame is always a snapshot"),
+            };
+            (current.clone().unwrap(), changed)
+        })
+    }
+
+    // Redraws each frame in place, highlighting the cells that changed
+    pub fn replay(&self, out: &mut impl Write, delay: Duration) -> Result<()> {
+        let total = self.len();
+        for (step, (board, changed)) in self.frames().enumerate() {
+            let text = Renderer::new(|_, cell: &T| cell.to_string())
+                .highlight(changed)
+                .render(&board);
+            write!(out, "\x1b[2J\x1b[HStep {}/{}\n{}", step + 1, total, text)?;
+            out.flush()?;
+            thread::sleep(delay);
+        }
+        Ok(())
+    }
+
+    // Writes frame_0000.ppm, frame_0001.ppm, ... into `dir` and returns their paths
+    pub fn export_frames(&self, dir: &str, palette: &Palette<T>) -> Result<Vec<String>> {
+        fs::create_dir_all(dir).with_context(|| format!("Can not create {}", dir))?;
+        self.frames()
+            .enumerate()
+            .map(|(step, (board, _))| {
+                let path = Path::new(dir).join(format!("frame_{:04}.ppm", step));
+                let path = path.to_string_lossy().to_string();
+                board.to_ppm(&path, palette)?;
+                Ok(path)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board::image::{Overlay, Palette, Rgb};
+    use crate::board::record::{changed_cells, Change, RecordMode, Recorder};
+    use crate::board::{Board, Coord};
+    use std::time::Duration;
+
+    fn steps() -> Vec<Board<char>> {
+        let mut board = Board::<char>::from_string("@@@\n...\n").unwrap();
+        let mut steps = vec![board.clone()];
+        for x in 0..2 {
+            board.set_value(Coord::new(x, 0), '.');
+            steps.push(board.clone());
+        }
+        steps
+    }
+
+    #[test]
+    fn test_snapshots_and_diffs_replay_the_same() {
+        for mode in [RecordMode::Snapshot, RecordMode::Diff] {
+            let mut recorder = Recorder::new(mode);
+            for board in steps() {
+                recorder.record(&board);
+            }
+            let frames: Vec<_> = recorder.frames().collect();
+            assert_eq!(frames.iter().map(|(b, _)| b.clone()).collect::<Vec<_>>(), steps());
+            assert_eq!(frames[2].1, vec![Coord::new(1, 0)]);
+        }
+
+        let mut recorder = Recorder::new(RecordMode::Diff);
+        steps().iter().for_each(|board| recorder.record(board));
+        let mut out: Vec<u8> = vec![];
+        recorder.replay(&mut out, Duration::ZERO).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("Step 3/3\n"));
+        assert!(out.ends_with("..@\n...\n"));
+    }
+
+    #[test]
+    fn test_resized_frame_is_a_snapshot() {
+        let small = Board::<char>::from_string("@.\n").unwrap();
+        let large = Board::<char>::from_string("@.\n.@\n").unwrap();
+        assert_eq!(changed_cells(&small, &large), Change::Resized);
+        assert_eq!(changed_cells(&large, &large), Change::Cells(vec![]));
+
+        let mut recorder = Recorder::new(RecordMode::Diff);
+        [&small, &large, &small].iter().for_each(|board| recorder.record(board));
+        let frames: Vec<_> = recorder.frames().collect();
+        assert_eq!(frames[1], (large.clone(), vec![]));
+        assert_eq!(frames[2], (small, vec![]));
+    }
+
+    #[test]
+    fn test_export_numbered_frames() {
+        let mut recorder = Recorder::new(RecordMode::Diff);
+        steps().iter().for_each(|board| recorder.record(board));
+        // Runs of the suite in parallel must not share the directory
+        let dir = std::env::temp_dir().join(format!("aoc25_export_frames_{}", std::process::id()));
+        let dir = dir.to_string_lossy();
+        let colour = |c: &char| if *c == '@' { Rgb(0, 0, 0) } else { Rgb(255, 255, 255) };
+        let palette = Palette { colour: &colour, scale: 1, overlay: Overlay::default() };
+        let paths = recorder.export_frames(&dir, &palette).unwrap();
+        assert_eq!(paths.len(), 3);
+        assert!(paths[2].ends_with("frame_0002.ppm"));
+        assert!(std::fs::read(&paths[2]).unwrap().starts_with(b"P6\n3 2\n255\n"));
+        std::fs::remove_dir_all(&*dir).unwrap();
+    }
+}
@@ -0,0 +1,147 @@
+use crate::board::{Board, Coord, NEIGHBORS4, NEIGHBORS8};
+use std::fmt::{Debug, Display};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Connectivity {
+    Four,
+    Eight,
+}
+
+impl Connectivity {
+    pub fn offsets(self) -> &'static [Coord] {
+        match self {
+            Connectivity::Four => &NEIGHBORS4,
+            Connectivity::Eight => &NEIGHBORS8,
+        }
+    }
+}
+
+// A connected group of cells. `min` and `max` are the corners of its bounding box,
+// and the perimeter counts the cell sides not shared with another cell of the group
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Component {
+    pub label: usize,
+    pub size: usize,
+    pub min: Coord,
+    pub max: Coord,
+    pub perimeter: usize,
+}
+
+impl<T> Board<T>
+where
+    T: PartialEq + Debug + Display + Copy,
+{
+    // Cells reachable from `start` through orthogonal steps, in visiting order
+    pub fn flood_fill(&self, start: Coord, predicate: impl Fn(Coord, &T) -> bool) -> Vec<Coord> {
+        let mut seen = vec![false; self.cells().len()];
+        self.fill_from(start, &predicate, Connectivity::Four, &mut seen)
+    }
+
+    fn fill_from(
+        &self,
+        start: Coord,
+        predicate: &impl Fn(Coord, &T) -> bool,
+        connectivity: Connectivity,
+        seen: &mut [bool],
+    ) -> Vec<Coord> {
+        let Some(start_idx) = self.index(start) else {
+            return vec![];
+        };
+        // On a wrapping board `start` may lie outside it
+        let start = self.coord_of(start_idx);
+        if seen[start_idx] || !predicate(start, &self.cells()[start_idx]) {
+            return vec![];
+        }
+        seen[start_idx] = true;
+        let mut region = vec![];
+        let mut stack = vec![start];
+        while let Some(coord) = stack.pop() {
+            region.push(coord);
+            for (next, value) in self.neighbors(coord, connectivity.offsets()) {
+                let next_idx = self.index(next).unwrap();
+                if !seen[next_idx] && predicate(next, value) {
+                    seen[next_idx] = true;
+                    stack.push(next);
+                }
+            }
+        }
+        region
+    }
+
+    // Labels every cell accepted by `predicate` with its component, numbered from 1 in
+    // reading order. Other cells get label 0, and `components[label - 1]` describes a label
+    pub fn label_components(
+        &self,
+        predicate: impl Fn(Coord, &T) -> bool,
+        connectivity: Connectivity,
+    ) -> (Board<usize>, Vec<Component>) {
+        let mut labels = Board::filled(self.width(), self.height(), 0).with_wrap(self.wrap());
+        let mut components: Vec<Component> = vec![];
+        let mut seen = vec![false; self.cells().len()];
+
+        for idx in 0..self.cells().len() {
+            let region = self.fill_from(self.coord_of(idx), &predicate, connectivity, &mut seen);
+            if region.is_empty() {
+                continue;
+            }
+            let label = components.len() + 1;
+            for &coord in &region {
+                labels.set_value(coord, label);
+            }
+            let perimeter = region
+                .iter()
+                .flat_map(|coord| coord.neighbors4())
+                .filter(|&side| labels.get_value(side) != Some(&label))
+                .count();
+            components.push(Component {
+                label,
+                size: region.len(),
+                min: Coord::new(
+                    region.iter().map(|c| c.x()).min().unwrap(),
+                    region.iter().map(|c| c.y()).min().unwrap(),
+                ),
+                max: Coord::new(
+                    region.iter().map(|c| c.x()).max().unwrap(),
+                    region.iter().map(|c| c.y()).max().unwrap(),
+                ),
+                perimeter,
+            });
+        }
+        (labels, components)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board::region::Connectivity;
+    use crate::board::{Board, Coord, Wrap};
+
+    #[test]
+    fn test_flood_fill() {
+        let board = Board::<char>::from_string("##.\n#.#\n...\n").unwrap();
+        let mut region = board.flood_fill(Coord::new(1, 0), |_, &c| c == '#');
+        region.sort_by_key(|c| (c.y(), c.x()));
+        assert_eq!(region, vec![Coord::new(0, 0), Coord::new(1, 0), Coord::new(0, 1)]);
+        assert!(board.flood_fill(Coord::new(2, 0), |_, &c| c == '#').is_empty());
+        assert_eq!(board.flood_fill(Coord::new(1, 1), |_, &c| c == '.').len(), 4);
+
+        let torus = board.with_wrap(Wrap::BOTH);
+        let mut region = torus.flood_fill(Coord::new(-2, 3), |_, &c| c == '#');
+        region.sort_by_key(|c| (c.y(), c.x()));
+        assert_eq!(region, vec![Coord::new(0, 0), Coord::new(1, 0), Coord::new(0, 1), Coord::new(2, 1)]);
+    }
+
+    #[test]
+    fn test_label_components() {
+        let board = Board::<char>::from_string("##.\n#.#\n...\n").unwrap();
+        let (labels, components) = board.label_components(|_, &c| c == '#', Connectivity::Four);
+        assert_eq!(labels.cells(), &[1, 1, 0, 1, 0, 2, 0, 0, 0]);
+        assert_eq!((components[0].size, components[0].perimeter), (3, 8));
+        assert_eq!((components[0].min, components[0].max), (Coord::new(0, 0), Coord::new(1, 1)));
+        assert_eq!((components[1].size, components[1].perimeter), (1, 4));
+
+        let (_, joined) = board.label_components(|_, &c| c == '#', Connectivity::Eight);
+        assert_eq!(joined.len(), 1);
+        assert_eq!((joined[0].size, joined[0].perimeter, joined[0].max), (4, 12, Coord::new(2, 1)));
+    }
+}
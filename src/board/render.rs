@@ -0,0 +1,115 @@
+use crate::board::{Board, Coord};
+use std::collections::HashSet;
+use std::fmt::{Debug, Display, Formatter};
+use std::io::IsTerminal;
+
+// Bold yellow on a dark background, then back to normal
+const HIGHLIGHT_START: &str = "\x1b[1;33;40m";
+const HIGHLIGHT_END: &str = "\x1b[0m";
+
+// `text` wrapped in the highlight colour when `on`
+pub(crate) fn paint(text: &str, on: bool) -> String {
+    if on {
+        format!("{}{}{}", HIGHLIGHT_START, text, HIGHLIGHT_END)
+    } else {
+        text.to_string()
+    }
+}
+
+// One row per line, cells separated by a space
+impl<T> Display for Board<T>
+where
+    T: PartialEq + Debug + Display + Copy,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            let line: Vec<String> = row.iter().map(|cell| cell.to_string()).collect();
+            writeln!(f, "{}", line.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> Board<T>
+where
+    T: PartialEq + Debug + Display + Copy,
+{
+    // Cells written back to back, as read by `Board::from_string`
+    pub fn to_string_compact(&self) -> String {
+        Renderer::new(|_, cell: &T| cell.to_string()).colour(false).render(self)
+    }
+}
+
+// Text for the cell at a coordinate
+type CellText<'a, T> = Box<dyn Fn(Coord, &T) -> String + 'a>;
+
+// Draws a board with a custom text for each cell. Highlighted cells are coloured when
+// colour is on, which by default means stdout is a terminal
+pub struct Renderer<'a, T> {
+    cell: CellText<'a, T>,
+    highlight: HashSet<Coord>,
+    colour: bool,
+}
+
+impl<'a, T> Renderer<'a, T>
+where
+    T: PartialEq + Debug + Display + Copy,
+{
+    pub fn new(cell: impl Fn(Coord, &T) -> String + 'a) -> Self {
+        Self {
+            cell: Box::new(cell),
+            highlight: HashSet::new(),
+            colour: std::io::stdout().is_terminal(),
+        }
+    }
+
+    pub fn highlight(mut self, coords: impl IntoIterator<Item = Coord>) -> Self {
+        self.highlight.extend(coords);
+        self
+    }
+
+    pub fn colour(mut self, colour: bool) -> Self {
+        self.colour = colour;
+        self
+    }
+
+    pub fn render(&self, board: &Board<T>) -> String {
+        let mut text = String::new();
+        for (y, row) in board.rows().enumerate() {
+            for (x, value) in row.iter().enumerate() {
+                let coord = Coord::new(x as i32, y as i32);
+                let cell = (self.cell)(coord, value);
+                text += &paint(&cell, self.colour && self.highlight.contains(&coord));
+            }
+            text.push('\n');
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board::render::Renderer;
+    use crate::board::{Board, Coord};
+
+    #[test]
+    fn test_display_and_compact_round_trip() {
+        let board = Board::<i32>::from_string("120\n345\n").unwrap();
+        assert_eq!(board.to_string(), "1 2 0\n3 4 5\n");
+        assert_eq!(board.to_string_compact(), "120\n345\n");
+        assert_eq!(Board::<i32>::from_string(&board.to_string_compact()).unwrap(), board);
+
+        let chars = Board::<char>::from_string("#.\n.#\n").unwrap();
+        assert_eq!(Board::<char>::from_string(&chars.to_string_compact()).unwrap(), chars);
+    }
+
+    #[test]
+    fn test_renderer_highlights() {
+        let board = Board::<char>::from_string("ab\ncd\n").unwrap();
+        let upper = |_, c: &char| c.to_ascii_uppercase().to_string();
+        let plain = Renderer::new(upper).highlight([Coord::new(1, 0)]).colour(false);
+        assert_eq!(plain.render(&board), "AB\nCD\n");
+        let coloured = Renderer::new(upper).highlight([Coord::new(1, 0)]).colour(true);
+        assert_eq!(coloured.render(&board), "A\x1b[1;33;40mB\x1b[0m\nCD\n");
+    }
+}
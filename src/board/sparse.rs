@@ -0,0 +1,150 @@
+use crate::board::view::Rect;
+use crate::board::{Board, Coord};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Debug, Display};
+
+// Unbounded grid that only stores the cells that were set. Every other cell reads as
+// `default`. Cells are kept sorted by (y, x), with a second index sorted by (x, y)
+#[derive(Clone, Debug)]
+pub struct SparseGrid<T> {
+    by_row: BTreeMap<(i32, i32), T>,
+    by_column: BTreeSet<(i32, i32)>,
+    default: T,
+}
+
+impl<T> SparseGrid<T>
+where
+    T: PartialEq + Debug + Display + Copy,
+{
+    pub fn new(default: T) -> Self {
+        Self { by_row: BTreeMap::new(), by_column: BTreeSet::new(), default }
+    }
+
+    pub fn len(&self) -> usize {
+        self.by_row.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_row.is_empty()
+    }
+
+    pub fn contains(&self, coord: Coord) -> bool {
+        self.by_row.contains_key(&(coord.y(), coord.x()))
+    }
+
+    // The stored value, or the default for cells never set
+    pub fn get(&self, coord: Coord) -> &T {
+        self.get_value(coord).unwrap_or(&self.default)
+    }
+
+    // Only cells that were set
+    pub fn get_value(&self, coord: Coord) -> Option<&T> {
+        self.by_row.get(&(coord.y(), coord.x()))
+    }
+
+    pub fn set_value(&mut self, coord: Coord, value: T) -> Option<T> {
+        self.by_column.insert((coord.x(), coord.y()));
+        self.by_row.insert((coord.y(), coord.x()), value)
+    }
+
+    pub fn remove(&mut self, coord: Coord) -> Option<T> {
+        self.by_column.remove(&(coord.x(), coord.y()));
+        self.by_row.remove(&(coord.y(), coord.x()))
+    }
+
+    // Smallest rectangle holding every stored cell
+    pub fn bounding_box(&self) -> Option<Rect> {
+        let (&(min_y, _), _) = self.by_row.first_key_value()?;
+        let (&(max_y, _), _) = self.by_row.last_key_value()?;
+        let &(min_x, _) = self.by_column.first()?;
+        let &(max_x, _) = self.by_column.last()?;
+        Some(Rect::from_corners(Coord::new(min_x, min_y), Coord::new(max_x, max_y)))
+    }
+
+    // Stored cells in reading order
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.by_row.iter().map(|(&(y, x), value)| (Coord::new(x, y), value))
+    }
+
+    // Stored cells of row `y`, left to right
+    pub fn row(&self, y: i32) -> impl Iterator<Item = (Coord, &T)> {
+        self.by_row
+            .range((y, i32::MIN)..=(y, i32::MAX))
+            .map(|(&(y, x), value)| (Coord::new(x, y), value))
+    }
+
+    // Stored cells of column `x`, top to bottom
+    pub fn column(&self, x: i32) -> impl Iterator<Item = (Coord, &T)> {
+        self.by_column
+            .range((x, i32::MIN)..=(x, i32::MAX))
+            .map(|&(x, y)| (Coord::new(x, y), &self.by_row[&(y, x)]))
+    }
+
+    // Dense copy of the bounding box and the coordinate of its top-left cell
+    pub fn to_board(&self) -> Option<(Board<T>, Coord)> {
+        let rect = self.bounding_box()?;
+        let mut board = Board::filled(rect.width, rect.height, self.default);
+        for (coord, &value) in self.iter() {
+            board.set_value(coord - rect.min, value);
+        }
+        Some((board, rect.min))
+    }
+
+    // Stores the cells of `board` that differ from `default`, with the board's top-left at `origin`
+    pub fn from_board(board: &Board<T>, origin: Coord, default: T) -> Self {
+        let mut grid = Self::new(default);
+        for (idx, &value) in board.cells().iter().enumerate() {
+            if value != default {
+                grid.set_value(origin + board.coord_of(idx), value);
+            }
+        }
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board::sparse::SparseGrid;
+    use crate::board::view::Rect;
+    use crate::board::{Board, Coord};
+
+    #[test]
+    fn test_sparse_grid_orders_and_bounds() {
+        let mut grid = SparseGrid::new('.');
+        for (x, y) in [(100_000, -5), (-3, 7), (4, 7), (-3, -5)] {
+            grid.set_value(Coord::new(x, y), '#');
+        }
+        assert_eq!(grid.get(Coord::new(0, 0)), &'.');
+        assert_eq!(grid.get_value(Coord::new(0, 0)), None);
+        assert_eq!(
+            grid.bounding_box(),
+            Some(Rect::from_corners(Coord::new(-3, -5), Coord::new(100_000, 7)))
+        );
+        assert_eq!(grid.row(7).map(|(c, _)| c.x()).collect::<Vec<_>>(), vec![-3, 4]);
+        assert_eq!(grid.column(-3).map(|(c, _)| c.y()).collect::<Vec<_>>(), vec![-5, 7]);
+
+        grid.remove(Coord::new(100_000, -5));
+        assert_eq!(grid.bounding_box().unwrap().width, 8);
+    }
+
+    #[test]
+    fn test_bounding_box_spans_every_i32() {
+        let mut grid = SparseGrid::new('.');
+        grid.set_value(Coord::new(i32::MIN, i32::MAX), '#');
+        grid.set_value(Coord::new(i32::MAX, i32::MIN), '#');
+        let rect = grid.bounding_box().unwrap();
+        assert_eq!((rect.min, rect.width, rect.height), (Coord::new(i32::MIN, i32::MIN), 1 << 32, 1 << 32));
+    }
+
+    #[test]
+    fn test_dense_round_trip() {
+        let board = Board::<char>::from_string("..#\n#..\n").unwrap();
+        let grid = SparseGrid::from_board(&board, Coord::new(10, 20), '.');
+        assert_eq!(grid.len(), 2);
+        assert!(grid.contains(Coord::new(12, 20)));
+        let (dense, origin) = grid.to_board().unwrap();
+        assert_eq!(origin, Coord::new(10, 20));
+        assert_eq!(dense, board);
+        assert!(SparseGrid::new(0).to_board().is_none());
+    }
+}
@@ -0,0 +1,85 @@
+use crate::board::Board;
+use std::fmt::{Debug, Display};
+
+impl<T> Board<T>
+where
+    T: PartialEq + Debug + Display + Copy,
+{
+    // New board of the given size where each cell (x, y) is copied from `source(x, y)`
+    fn remap(&self, width: usize, height: usize, source: impl Fn(usize, usize) -> (usize, usize)) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let (sx, sy) = source(x, y);
+                self.cells[sy * self.width + sx]
+            })
+            .collect();
+        Self::from_parts(cells, width, height).with_wrap(self.wrap)
+    }
+
+    // Quarter turn clockwise, as printed
+    pub fn rotate_cw(&self) -> Self {
+        let height = self.height;
+        self.remap(self.height, self.width, |x, y| (y, height - 1 - x))
+            .with_wrap(self.wrap.swapped())
+    }
+
+    pub fn rotate_ccw(&self) -> Self {
+        let width = self.width;
+        self.remap(self.height, self.width, |x, y| (width - 1 - y, x))
+            .with_wrap(self.wrap.swapped())
+    }
+
+    // Mirrors left and right
+    pub fn flip_horizontal(&self) -> Self {
+        let width = self.width;
+        self.remap(self.width, self.height, |x, y| (width - 1 - x, y))
+    }
+
+    // Mirrors top and bottom
+    pub fn flip_vertical(&self) -> Self {
+        let height = self.height;
+        self.remap(self.width, self.height, |x, y| (x, height - 1 - y))
+    }
+
+    // The distinct boards among the four rotations of the board and of its mirror image
+    pub fn orientations(&self) -> impl Iterator<Item = Self> {
+        let mut result: Vec<Self> = Vec::with_capacity(8);
+        for start in [self.clone(), self.flip_horizontal()] {
+            let mut current = start;
+            for _ in 0..4 {
+                let next = current.rotate_cw();
+                if !result.contains(&current) {
+                    result.push(current);
+                }
+                current = next;
+            }
+        }
+        result.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board::Board;
+
+    #[test]
+    fn test_rotations_and_flips() {
+        let board = Board::<char>::from_string("ab\ncd\nef\n").unwrap();
+        assert_eq!(board.rotate_cw(), Board::<char>::from_string("eca\nfdb\n").unwrap());
+        assert_eq!(board.rotate_ccw(), Board::<char>::from_string("bdf\nace\n").unwrap());
+        assert_eq!(board.flip_horizontal(), Board::<char>::from_string("ba\ndc\nfe\n").unwrap());
+        assert_eq!(board.flip_vertical(), Board::<char>::from_string("ef\ncd\nab\n").unwrap());
+        assert_eq!(board.rotate_cw().rotate_ccw(), board);
+        assert_eq!(board.rotate_cw().rotate_cw(), board.flip_horizontal().flip_vertical());
+    }
+
+    #[test]
+    fn test_orientations_skip_symmetric_duplicates() {
+        let count = |text: &str| Board::<char>::from_string(text).unwrap().orientations().count();
+        assert_eq!(count("ab\ncd\n"), 8);
+        assert_eq!(count("#.\n##\n"), 4);
+        assert_eq!(count("#.\n.#\n"), 2);
+        assert_eq!(count("##\n##\n"), 1);
+    }
+}
@@ -0,0 +1,175 @@
+use crate::board::{Board, Coord};
+use std::fmt::{Debug, Display};
+
+// Rectangle of cells with its top-left corner at `min`
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Rect {
+    pub min: Coord,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Rect {
+    pub fn new(min: Coord, width: usize, height: usize) -> Self {
+        Self { min, width, height }
+    }
+
+    // Both corners included. The sides are measured in i64, as they can span every i32
+    pub fn from_corners(min: Coord, max: Coord) -> Self {
+        let side = |from: i32, to: i32| (to as i64 - from as i64 + 1).max(0) as usize;
+        Self::new(min, side(min.x(), max.x()), side(min.y(), max.y()))
+    }
+
+    pub fn contains(&self, coord: Coord) -> bool {
+        let d = coord - self.min;
+        d.x() >= 0 && d.y() >= 0 && (d.x() as usize) < self.width && (d.y() as usize) < self.height
+    }
+}
+
+// Borrowed window into a board. Coordinates are relative to the window's top-left corner
+#[derive(Clone, Copy, Debug)]
+pub struct BoardView<'a, T> {
+    board: &'a Board<T>,
+    rect: Rect,
+}
+
+impl<'a, T> BoardView<'a, T>
+where
+    T: PartialEq + Debug + Display + Copy,
+{
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    pub fn width(&self) -> usize {
+        self.rect.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.rect.height
+    }
+
+    pub fn get_value(&self, coord: Coord) -> Option<&'a T> {
+        let inside = Rect::new(Coord::new(0, 0), self.rect.width, self.rect.height);
+        if !inside.contains(coord) {
+            return None;
+        }
+        self.board.get_value(self.rect.min + coord)
+    }
+
+    pub fn row(&self, y: usize) -> &'a [T] {
+        assert!(y < self.rect.height, "Row {} is outside the view", y);
+        let x = self.rect.min.x() as usize;
+        &self.board.row(self.rect.min.y() as usize + y)[x..x + self.rect.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> + '_ {
+        (0..self.rect.height).map(|y| self.row(y))
+    }
+
+    // Cells in reading order
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + '_ {
+        self.rows().flatten()
+    }
+
+    pub fn to_board(&self) -> Board<T> {
+        Board::from_parts(
+            self.iter().copied().collect(),
+            if self.rect.height == 0 { 0 } else { self.rect.width },
+            if self.rect.width == 0 { 0 } else { self.rect.height },
+        )
+    }
+}
+
+impl<T> Board<T>
+where
+    T: PartialEq + Debug + Display + Copy,
+{
+    // None unless the whole rectangle lies on the board
+    pub fn view(&self, rect: Rect) -> Option<BoardView<'_, T>> {
+        let fits = rect.min.x() >= 0
+            && rect.min.y() >= 0
+            && rect.min.x() as usize + rect.width <= self.width
+            && rect.min.y() as usize + rect.height <= self.height;
+        fits.then_some(BoardView { board: self, rect })
+    }
+
+    // Top-left corners of every place where `pattern` matches. Pattern cells equal to
+    // `wildcard` match anything
+    pub fn find_all_pattern(&self, pattern: &Board<T>, wildcard: Option<T>) -> Vec<Coord> {
+        // An empty pattern would match everywhere, even one past the last row and column
+        if pattern.width == 0 || pattern.width > self.width || pattern.height > self.height {
+            return vec![];
+        }
+        let mut found = vec![];
+        for y in 0..=self.height - pattern.height {
+            for x in 0..=self.width - pattern.width {
+                let min = Coord::new(x as i32, y as i32);
+                let view = self.view(Rect::new(min, pattern.width, pattern.height)).unwrap();
+                let matches = view.rows().zip(pattern.rows()).all(|(cells, expected)| {
+                    cells
+                        .iter()
+                        .zip(expected)
+                        .all(|(cell, want)| Some(*want) == wildcard || cell == want)
+                });
+                if matches {
+                    found.push(min);
+                }
+            }
+        }
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board::view::Rect;
+    use crate::board::{Board, Coord};
+
+    #[test]
+    fn test_view_reads_without_copying() {
+        let board = Board::<i32>::from_string("1234\n5678\n9012\n").unwrap();
+        let view = board.view(Rect::from_corners(Coord::new(1, 1), Coord::new(3, 2))).unwrap();
+        assert_eq!((view.width(), view.height()), (3, 2));
+        assert_eq!(view.get_value(Coord::new(0, 0)), Some(&6));
+        assert_eq!(view.get_value(Coord::new(3, 0)), None);
+        assert_eq!(view.rows().collect::<Vec<_>>(), vec![&[6, 7, 8], &[0, 1, 2]]);
+        assert_eq!(view.iter().sum::<i32>(), 24);
+        assert_eq!(view.to_board(), Board::<i32>::from_string("678\n012\n").unwrap());
+        assert!(board.view(Rect::new(Coord::new(2, 2), 3, 1)).is_none());
+    }
+
+    #[test]
+    fn test_rect_from_far_corners() {
+        let rect = Rect::from_corners(Coord::new(i32::MIN, 0), Coord::new(i32::MAX, -1));
+        assert_eq!((rect.width, rect.height), (1 << 32, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Row 2 is outside the view")]
+    fn test_view_row_outside() {
+        let board = Board::<i32>::from_string("1234\n5678\n9012\n").unwrap();
+        board.view(Rect::new(Coord::new(0, 0), 4, 2)).unwrap().row(2);
+    }
+
+    #[test]
+    fn test_find_all_pattern() {
+        let board = Board::<char>::from_string("#.#.\n.#.#\n#.#.\n").unwrap();
+        let diagonal = Board::<char>::from_string("#.\n.#\n").unwrap();
+        assert_eq!(
+            board.find_all_pattern(&diagonal, None),
+            vec![Coord::new(0, 0), Coord::new(2, 0), Coord::new(1, 1)]
+        );
+        let corner = Board::<char>::from_string("#?\n?#\n").unwrap();
+        assert_eq!(board.find_all_pattern(&corner, Some('?')).len(), 3);
+        let any = Board::<char>::from_string("??\n").unwrap();
+        assert_eq!(board.find_all_pattern(&any, Some('?')).len(), 9);
+    }
+
+    #[test]
+    fn test_find_all_pattern_empty() {
+        let board = Board::<char>::from_string("#.#.\n.#.#\n").unwrap();
+        assert!(board.find_all_pattern(&Board::filled(0, 0, '#'), None).is_empty());
+        assert!(board.find_all_pattern(&Board::filled(5, 1, '#'), None).is_empty());
+    }
+}
@@ -1,33 +1,48 @@
-use anyhow::Result;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use crate::answer::Answer;
+use anyhow::{bail, Context, Result};
 
-fn read_file(path: &str) -> Vec<i32> {
-    let mut numbers: Vec<i32> = Vec::new();
+pub(crate) fn parse(content: &str) -> Result<Vec<i64>> {
+    let mut numbers: Vec<i64> = Vec::new();
 
-    let file = File::open(path);
-    let reader = BufReader::new(file.unwrap());
-    for line in reader.lines() {
-        let cleaned = line.unwrap().replace("R", "").replace("L", "-");
-
-        let number: i32 = cleaned.trim().parse().unwrap();
-        numbers.push(number);
+    for (idx, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let sign = match line.chars().next() {
+            Some('R') => 1,
+            Some('L') => -1,
+            _ => bail!("Line {}: rotation must start with R or L: {:?}", idx + 1, line),
+        };
+        // The direction already gives the sign, so the distance must not have one
+        let distance = &line[1..];
+        if distance.starts_with(['+', '-']) {
+            bail!("Line {}: distance must not be signed: {:?}", idx + 1, line);
+        }
+        let number: u32 = distance
+            .parse()
+            .with_context(|| format!("Line {}: invalid distance: {:?}", idx + 1, line))?;
+        numbers.push(sign * number as i64);
     }
-    numbers
+    Ok(numbers)
+}
+
+#[cfg(test)]
+fn read_file(path: &str) -> Result<Vec<i64>> {
+    parse(&std::fs::read_to_string(path)?)
 }
 
-fn day1(numbers: &Vec<i32>) -> (i32, i32) {
-    let mut current: i32 = 50;
-    let mut last_number: i32 = current;
-    let (mut count_1, mut count_2): (i32, i32) = (0, 0);
+fn day1(numbers: &Vec<i64>) -> (i64, i64) {
+    let mut current: i64 = 50;
+    let (mut count_1, mut count_2): (i64, i64) = (0, 0);
 
     for number in numbers {
-        last_number = current;
+        let last_number = current;
         current += number;
 
         count_2 += part2(current, last_number);
 
-        current = ((current % 100) + 100) % 100;
+        current = current.rem_euclid(100);
         if current == 0 {
             count_1 += 1;
         }
@@ -35,7 +50,7 @@ fn day1(numbers: &Vec<i32>) -> (i32, i32) {
     (count_1, count_2)
 }
 
-fn part2(current: i32, last_number: i32) -> i32 {
+fn part2(current: i64, last_number: i64) -> i64 {
     match (current, last_number) {
         (current, 0) => current.abs() / 100,
         (0, _) => 1,
@@ -45,12 +60,13 @@ fn part2(current: i32, last_number: i32) -> i32 {
     }
 }
 
-pub fn solve() -> Result<()> {
-    let input_day1 = read_file("inputs/day1.txt");
-    let (res_part1, res_part2) = day1(&input_day1);
-    println!("Part 1: {}", res_part1);
-    println!("Part 2: {}", res_part2);
-    Ok(())
+pub fn answers(input: &str) -> Result<Vec<Answer>> {
+    let (part1, part2) = day1(&parse(input)?);
+    Ok(vec![part1.into(), part2.into()])
+}
+
+pub fn solve() -> Result<[Answer; 2]> {
+    super::solve_file("inputs/day1.txt", answers)
 }
 
 #[cfg(test)]
@@ -59,7 +75,7 @@ mod tests {
 
     #[test]
     fn test_day1() {
-        let input_day1 = read_file("test/test_day01.txt");
+        let input_day1 = read_file("test/test_day01.txt").unwrap();
         let result = day1(&input_day1);
         assert_eq!(result, (4, 24));
     }
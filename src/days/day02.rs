@@ -1,25 +1,33 @@
-use anyhow::Result;
-use std::fs;
+use crate::answer::Answer;
+use crate::bignum::Count;
+use anyhow::{anyhow, Context, Result};
+use std::collections::BTreeSet;
 
-fn read_file(path: &str) -> Vec<(i64, i64)> {
-    let file_content = fs::read_to_string(path).unwrap();
-    file_content
+pub(crate) fn parse(content: &str) -> Result<Vec<(i64, i64)>> {
+    content
         .trim()
         .split(",")
         .map(|range| {
-            let mut parts = range
-                .split("-")
-                .map(|x| x
-                    .trim()
+            let (start, end) = range
+                .split_once("-")
+                .ok_or_else(|| anyhow!("Range without '-': {:?}", range))?;
+            let parse_id = |x: &str| {
+                x.trim()
                     .parse::<i64>()
-                    .unwrap());
-            (parts.next().unwrap(), parts.next().unwrap())
+                    .with_context(|| format!("Invalid ID in range {:?}", range))
+            };
+            Ok((parse_id(start)?, parse_id(end)?))
         })
         .collect()
 }
 
+#[cfg(test)]
+fn read_file(path: &str) -> Result<Vec<(i64, i64)>> {
+    parse(&std::fs::read_to_string(path)?)
+}
+
 fn find_duplicated(number: &str, digits: usize) -> bool{
-    if number.len() % digits != 0 {return false;}
+    if !number.len().is_multiple_of(digits) {return false;}
     let parts: Vec<&str> = (0..number.len())
         .step_by(digits)
         .map(|i| &number[i..i+digits])
@@ -40,38 +48,72 @@ fn is_invalid_all(number: &i64) -> bool {
 fn is_invalid(number: &i64) -> bool {
     let str_number = number.to_string();
     let len = str_number.len();
-    if len % 2 == 0{
+    if len.is_multiple_of(2){
         return find_duplicated(str_number.as_str(), len/2);
     }
     false
 }
 
-fn day2(numbers: Vec<(i64, i64)>, f: fn(&i64) -> bool) -> i64 {
+// Numbers in [lo, hi] made of one block of digits repeated at least twice.
+// Only the blocks are enumerated, so wide ranges stay cheap.
+fn repeated_numbers(lo: i64, hi: i64) -> BTreeSet<i64> {
+    let mut numbers = BTreeSet::new();
+    for len in 2..=19u32 {
+        let from = (lo as i128).max(10i128.pow(len - 1));
+        let to = (hi as i128).min(10i128.pow(len) - 1);
+        if from > to {
+            continue;
+        }
+        for block in (1..=len / 2).filter(|block| len.is_multiple_of(*block)) {
+            // 1010101 repeats a block of two digits four times
+            let unit: i128 = (0..len / block).map(|k| 10i128.pow(block * k)).sum();
+            for pattern in (from + unit - 1) / unit..=to / unit {
+                numbers.insert((pattern * unit) as i64);
+            }
+        }
+    }
+    numbers
+}
+
+// Wide ranges can hold enough invalid IDs to overflow an i64 sum
+fn day2(numbers: Vec<(i64, i64)>, f: fn(&i64) -> bool) -> Count {
     numbers
         .iter()
-        .map(|(r1, r2)|
-            (*r1..*r2+1)
-            .filter(|x| f(x))
-                .sum::<i64>()
-        )
+        .flat_map(|&(r1, r2)| repeated_numbers(r1, r2))
+        .filter(f)
+        .map(|n| Count::from(n as u64))
         .sum()
 }
 
-pub fn solve() -> Result<()> {
-    let numbers = read_file("inputs/day02.txt");
-    println!("Part1: {}", day2(numbers.clone(), is_invalid));
-    println!("Part2: {}", day2(numbers, is_invalid_all));
-    Ok(())
+pub fn answers(input: &str) -> Result<Vec<Answer>> {
+    let numbers = parse(input)?;
+    Ok(vec![
+        day2(numbers.clone(), is_invalid).into(),
+        day2(numbers, is_invalid_all).into(),
+    ])
+}
+
+pub fn solve() -> Result<[Answer; 2]> {
+    super::solve_file("inputs/day02.txt", answers)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::days::day02::{day2, is_invalid, is_invalid_all, read_file};
+    use crate::days::day02::{day2, is_invalid, is_invalid_all, read_file, repeated_numbers};
 
     #[test]
     fn test_day1(){
-        let numbers = read_file("test/test_day02.txt");
+        let numbers = read_file("test/test_day02.txt").unwrap();
         assert_eq!(day2(numbers.clone(), is_invalid), 1227775554);
         assert_eq!(day2(numbers, is_invalid_all), 4174379265);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_repeated_numbers_match_brute_force() {
+        for (lo, hi) in [(1, 5000), (95, 115), (998, 1012), (1188511880, 1188511890)] {
+            let expected: Vec<i64> = (lo..=hi).filter(is_invalid_all).collect();
+            assert_eq!(repeated_numbers(lo, hi).into_iter().collect::<Vec<_>>(), expected);
+        }
+        assert!(repeated_numbers(20, 10).is_empty());
+    }
+}
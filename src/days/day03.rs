@@ -1,9 +1,12 @@
+use crate::answer::Answer;
+use crate::bignum::Count;
 use crate::board::Board;
-use anyhow::Result;
+use anyhow::{bail, Result};
 
-fn find_max_joltage(row: &[i32], n: usize, result: u64) -> u64 {
+// The joltage is built digit by digit, so large `n` promotes to a big integer
+fn find_max_joltage(row: &[i32], n: usize, result: Count) -> Count {
     if n == 1 {
-        return result + row.iter().max().unwrap().clone() as u64;
+        return result * Count::from(10) + Count::from(*row.iter().max().unwrap() as u64);
     }
 
     let numbers = &row[..(row.len()-n+1)];
@@ -11,21 +14,30 @@ fn find_max_joltage(row: &[i32], n: usize, result: u64) -> u64 {
     let pos_value = numbers.iter().position(|&x| x == max_value).unwrap();
     find_max_joltage(&row[(pos_value+1)..],
                                n-1,
-                               result + (max_value as u64 * 10u64.pow(n as u32 - 1)))
+                               result * Count::from(10) + Count::from(max_value as u64))
 }
 
-fn day3(board: &Board<i32>, n: usize) -> u64 {
-    board.rows
-        .iter()
-        .map(|row| find_max_joltage(row.as_slice(), n, 0))
+pub(crate) fn parse(content: &str) -> Result<Board<i32>> {
+    let board = Board::<i32>::from_string(content)?;
+    if board.width() < 12 {
+        bail!("Banks have fewer than 12 batteries");
+    }
+    Ok(board)
+}
+
+fn day3(board: &Board<i32>, n: usize) -> Count {
+    board.rows()
+        .map(|row| find_max_joltage(row, n, Count::default()))
         .sum()
 }
 
-pub fn solve() -> Result<()> {
-    let matrix = Board::read_int_board("inputs/day03.txt");
-    println!("Parte 1: {}", day3(&matrix, 2));
-    println!("Parte 12: {}", day3(&matrix, 12));
-    Ok(())
+pub fn answers(input: &str) -> Result<Vec<Answer>> {
+    let matrix = parse(input)?;
+    Ok(vec![day3(&matrix, 2).into(), day3(&matrix, 12).into()])
+}
+
+pub fn solve() -> Result<[Answer; 2]> {
+    super::solve_file("inputs/day03.txt", answers)
 }
 
 #[cfg(test)]
@@ -35,8 +47,14 @@ mod tests {
 
     #[test]
     fn test_day3(){
-        let board = Board::read_int_board("test/test_day03.txt");
+        let board = Board::read_int_board("test/test_day03.txt").unwrap();
         assert_eq!(day3(&board, 2), 357);
         assert_eq!(day3(&board, 12), 3121910778619);
     }
+
+    #[test]
+    fn test_day3_beyond_u64() {
+        let board = Board::new(vec![vec![9; 30]]);
+        assert_eq!(day3(&board, 25).to_string(), "9".repeat(25));
+    }
 }
@@ -1,76 +1,90 @@
-use crate::board::{Board, Coord};
-use anyhow::Result;
+use crate::answer::Answer;
+use crate::board::automaton::{Automaton, Neighbourhood};
+use crate::board::bits::BitBoard;
+use crate::board::{Board, RowShape};
+use anyhow::{bail, Result};
 
-fn surrounding_cells(board: &Board<char>, coord: Coord) -> i32 {
-    [
-        coord.up(),
-        coord.down(),
-        coord.left(),
-        coord.right(),
-        Coord::new(coord.x + 1, coord.y + 1),
-        Coord::new(coord.x + 1, coord.y - 1),
-        Coord::new(coord.x - 1, coord.y + 1),
-        Coord::new(coord.x - 1, coord.y - 1),
-    ]
-    .iter()
-    .map(|&adj_coord| board.get_value(adj_coord))
-    .map(|x| match x {
-        Some('@') => 1,
-        _ => 0,
+// A roll with fewer than four rolls around it can be removed
+fn removal() -> Automaton<'static, char> {
+    Automaton::new(|&cell, neighbours: &Neighbourhood<char>| {
+        if cell == '@' && neighbours.count('@') < 4 {
+            '.'
+        } else {
+            cell
+        }
     })
-    .sum::<i32>()
 }
 
-
 fn remove_rolls(grid: &mut Board<char>) -> i32 {
-    let grid_ref: &Board<char> = &grid.clone();
-    grid.rows
-        .iter_mut()
-        .enumerate()
-        .flat_map(|(y, row)| {
-            row.iter_mut()
-                .enumerate()
-                .filter_map(move |(x, v)| {
-                    let coord = Coord::new(x as i32, y as i32);
-                    if surrounding_cells(&grid_ref, coord) < 4 && *v == '@' {
-                        *v = '.';
-                        Some(())
-                    } else {
-                        None
-                    }
-                })
-        })
-        .count() as i32
+    removal().step(grid) as i32
 }
 
-fn remove_rolls_as_possible(grid: &mut Board<char>) -> i32 {
-    let mut count: i32 = 0;
+// Same rule on a bit board, removing every roll with fewer than four neighbours each round.
+// Returns the rolls removed in each round until none can be removed
+fn remove_rolls_bits(grid: &Board<char>) -> Vec<usize> {
+    let mut rolls = BitBoard::from_board(grid, |&c| c == '@');
+    let mut removed = vec![];
     loop {
-        let act_count = remove_rolls(grid);
-        count += act_count;
-        if act_count == 0 {
-            break;
+        let removable = &rolls & &rolls.neighbour_counts().fewer_than(4);
+        if removable.is_empty() {
+            return removed;
         }
+        removed.push(removable.count_ones());
+        rolls = rolls.and_not(&removable);
     }
-    count
 }
 
-pub fn solve() -> Result<()> {
-    let grid = Board::read_char_board("inputs/day04.txt");
-    println!("Part1: {}", remove_rolls(&mut grid.clone()));
-    println!("Part2: {}", remove_rolls_as_possible(&mut grid.clone()));
-    Ok(())
+pub(crate) fn parse(content: &str) -> Result<Board<char>> {
+    Board::parse(content, &|c| match c {
+        '@' | '.' => Ok(c),
+        _ => bail!("expected '@' or '.'"),
+    }, RowShape::Rectangular)
+}
+
+fn remove_rolls_as_possible(grid: &mut Board<char>) -> i32 {
+    removal()
+        .run_until_stable(grid)
+        .iter()
+        .map(|stats| stats.changed as i32)
+        .sum()
+}
+
+fn solve_bits(grid: &Board<char>) -> [Answer; 2] {
+    let removed = remove_rolls_bits(grid);
+    [
+        removed.first().copied().unwrap_or(0).into(),
+        removed.iter().sum::<usize>().into(),
+    ]
+}
+
+pub fn answers(input: &str) -> Result<Vec<Answer>> {
+    Ok(solve_bits(&parse(input)?).to_vec())
+}
+
+// Reference implementation on the generic automaton engine. Used by `diff-test`.
+pub fn reference_answers(input: &str) -> Result<Vec<Answer>> {
+    let grid = parse(input)?;
+    Ok(vec![
+        remove_rolls(&mut grid.clone()).into(),
+        remove_rolls_as_possible(&mut grid.clone()).into(),
+    ])
+}
+
+pub fn solve() -> Result<[Answer; 2]> {
+    super::solve_file("inputs/day04.txt", answers)
 }
 
 #[cfg(test)]
 mod test {
     use crate::board::Board;
-    use crate::days::day04::{remove_rolls, remove_rolls_as_possible};
+    use crate::days::day04::{remove_rolls, remove_rolls_as_possible, remove_rolls_bits};
 
     #[test]
     fn test_day4(){
-        let grid = Board::read_char_board("test/test_day04.txt");
+        let grid = Board::read_char_board("test/test_day04.txt").unwrap();
         assert_eq!(remove_rolls(&mut grid.clone()), 13);
         assert_eq!(remove_rolls_as_possible(&mut grid.clone()), 43);
+        let removed = remove_rolls_bits(&grid);
+        assert_eq!((removed[0], removed.iter().sum::<usize>()), (13, 43));
     }
 }
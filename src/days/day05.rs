@@ -1,33 +1,51 @@
-use anyhow::Result;
-use std::fs;
+use crate::answer::Answer;
+use crate::bignum::Count;
+use anyhow::{anyhow, bail, Context, Result};
 
-fn read_ranges(content: &str) -> Vec<(i64, i64)> {
+// Fresh ID ranges and the available ingredient IDs
+type Inventory = (Vec<(i64, i64)>, Vec<i64>);
+
+fn read_ranges(content: &str) -> Result<Vec<(i64, i64)>> {
     content
         .lines()
         .map(|line| {
-            let mut parts = line.split("-");
-            let r1: i64 = parts.next().unwrap().parse().unwrap_or(0);
-            let r2: i64 = parts.next().unwrap().parse().unwrap_or(0);
-            (r1, r2)
+            let (r1, r2) = line
+                .split_once("-")
+                .ok_or_else(|| anyhow!("Range without '-': {:?}", line))?;
+            let r1: i64 = r1.trim().parse().with_context(|| format!("Invalid range {:?}", line))?;
+            let r2: i64 = r2.trim().parse().with_context(|| format!("Invalid range {:?}", line))?;
+            if r1 > r2 {
+                bail!("Range {:?} ends before it starts", line);
+            }
+            Ok((r1, r2))
         })
         .collect()
 }
 
-fn read_file(path: &str) -> (Vec<(i64, i64)>, Vec<i64>) {
-    let file_content = fs::read_to_string(path).unwrap();
-
+pub(crate) fn parse(file_content: &str) -> Result<Inventory> {
     let mut parts = file_content.split("\n\n"); // Only it works with LF separator in .txt
     let first_part = parts.next().unwrap_or("").trim();
     let second_part = parts.next().unwrap_or("").trim();
-    let ranges = read_ranges(first_part);
+    if parts.any(|part| !part.trim().is_empty()) {
+        bail!("Expected ranges and IDs separated by a single blank line");
+    }
+    let ranges = read_ranges(first_part)?;
+    if ranges.is_empty() {
+        bail!("No fresh ranges");
+    }
     let values = second_part
         .lines()
-        .map(|line| line.parse::<i64>().unwrap_or(0))
-        .collect();
-    (ranges, values)
+        .map(|line| line.trim().parse::<i64>().with_context(|| format!("Invalid ID {:?}", line)))
+        .collect::<Result<_>>()?;
+    Ok((ranges, values))
+}
+
+#[cfg(test)]
+fn read_file(path: &str) -> Result<Inventory> {
+    parse(&std::fs::read_to_string(path)?)
 }
 
-fn clean_ranges(ranges: &mut Vec<(i64, i64)>) -> Vec<(i64, i64)> {
+fn clean_ranges(ranges: &mut [(i64, i64)]) -> Vec<(i64, i64)> {
     ranges.sort_by_key(|&(r1, _)| r1);
     let mut cleaned: Vec<(i64, i64)> = vec![ranges[0]];
 
@@ -43,15 +61,16 @@ fn clean_ranges(ranges: &mut Vec<(i64, i64)>) -> Vec<(i64, i64)> {
     cleaned
 }
 
-fn fresh_ids(ranges: &Vec<(i64, i64)>) -> i64 {
-    let cleaned = clean_ranges(&mut ranges.clone());
+// Ranges can cover nearly every i64, so their lengths are added up as a Count
+fn fresh_ids(ranges: &[(i64, i64)]) -> Count {
+    let cleaned = clean_ranges(&mut ranges.to_vec());
     cleaned
         .iter()
-        .map(|&(r1, r2)| (r2 - r1) + 1)
+        .map(|&(r1, r2)| Count::from(r2.abs_diff(r1)) + Count::from(1))
         .sum()
 }
 
-fn fresh_ingredients(ranges: &Vec<(i64, i64)>, values: &Vec<i64>) -> i64 {
+fn fresh_ingredients(ranges: &[(i64, i64)], values: &[i64]) -> i64 {
     values
         .iter()
         .filter(|v| ranges
@@ -60,21 +79,33 @@ fn fresh_ingredients(ranges: &Vec<(i64, i64)>, values: &Vec<i64>) -> i64 {
         .count() as i64
 }
 
-pub fn solve() -> Result<()> {
-    let (ranges, values) = read_file("inputs/day05.txt");
-    println!("Part 1: {}", fresh_ingredients(&ranges, &values));
-    println!("Part 2: {}", fresh_ids(&ranges));
-    Ok(())
+pub fn answers(input: &str) -> Result<Vec<Answer>> {
+    let (ranges, values) = parse(input)?;
+    Ok(vec![
+        fresh_ingredients(&ranges, &values).into(),
+        fresh_ids(&ranges).into(),
+    ])
+}
+
+pub fn solve() -> Result<[Answer; 2]> {
+    super::solve_file("inputs/day05.txt", answers)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::days::day05::{fresh_ingredients, read_file, fresh_ids};
+    use crate::days::day05::{fresh_ingredients, parse, read_file, fresh_ids};
 
     #[test]
     fn test_day5() {
-        let (ranges, values) = read_file("test/test_day05.txt");
+        let (ranges, values) = read_file("test/test_day05.txt").unwrap();
         assert_eq!(fresh_ingredients(&ranges, &values), 3);
         assert_eq!(fresh_ids(&ranges), 14);
     }
+
+    #[test]
+    fn test_day5_wide_and_reversed_ranges() {
+        let (ranges, _) = parse("0-9223372036854775807\n5-9\n\n1\n").unwrap();
+        assert_eq!(fresh_ids(&ranges).to_string(), "9223372036854775808");
+        assert!(parse("10-3\n\n1\n").is_err());
+    }
 }
@@ -1,57 +1,69 @@
-use crate::board::Board;
-use anyhow::Result;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use crate::answer::Answer;
+use crate::bignum::Count;
+use crate::board::{Board, RowShape};
+use anyhow::{bail, Context, Result};
 
-fn operate(row: &Vec<i64>, operation: String) -> i64 {
+fn operate(row: &[u64], operation: String) -> Count {
+    let values = row.iter().map(|&x| Count::from(x));
     match operation.as_ref() {
-        "+" => row.iter().sum(),
-        "*" => row.iter().product(),
+        "+" => values.sum(),
+        "*" => values.product(),
         _ => panic!("Unknown operation"),
     }
 }
 
-fn operate_numbers(n1: i64, n2: i64, operation: String) -> i64 {
-    match operation.as_ref() {
-        "+" => n1 + n2,
-        "*" => n1 * n2,
-        _ => panic!("Unknown operation"),
+pub(crate) fn parse(content: &str) -> Result<(Vec<Vec<u64>>, Vec<String>)> {
+    let mut content_line: Vec<&str> = content.lines().collect();
+    while content_line.last().is_some_and(|line| line.trim().is_empty()) {
+        content_line.pop();
     }
-}
-
-fn read_file(path: &str) -> (Vec<Vec<i64>>, Vec<String>) {
-    let file = File::open(path);
-    let reader = BufReader::new(file.unwrap());
-    let mut content_line: Vec<String> = reader.lines().map(|line| line.unwrap()).collect();
-    let operation_line: Vec<String> = content_line
-        .pop()
-        .unwrap()
+    let Some(last_line) = content_line.pop() else {
+        bail!("Empty worksheet");
+    };
+    let operation_line: Vec<String> = last_line
         .split_whitespace()
         .map(|s| s.to_string())
         .collect();
+    if let Some(operation) = operation_line.iter().find(|op| *op != "+" && *op != "*") {
+        bail!("Unknown operation {:?}", operation);
+    }
+    if content_line.is_empty() {
+        bail!("Worksheet without numbers");
+    }
 
-    let numbers: Vec<Vec<i64>> = content_line
+    let numbers: Vec<Vec<u64>> = content_line
         .iter()
-        .map(|line| {
-            line.split_whitespace()
-                .map(|x| x.parse::<i64>().unwrap())
-                .collect()
+        .enumerate()
+        .map(|(idx, line)| {
+            let row: Vec<u64> = line
+                .split_whitespace()
+                .map(|x| x.parse::<u64>().with_context(|| format!("Line {}: invalid number {:?}", idx + 1, x)))
+                .collect::<Result<_>>()?;
+            if row.len() != operation_line.len() {
+                bail!("Line {}: {} numbers for {} operations", idx + 1, row.len(), operation_line.len());
+            }
+            Ok(row)
         })
-        .collect();
-    (transpose(numbers), operation_line)
+        .collect::<Result<_>>()?;
+    Ok((transpose(numbers), operation_line))
+}
+
+#[cfg(test)]
+fn read_file(path: &str) -> Result<(Vec<Vec<u64>>, Vec<String>)> {
+    parse(&std::fs::read_to_string(path)?)
 }
 
-fn transpose(vec: Vec<Vec<i64>>) -> Vec<Vec<i64>> {
-    let mut transposed: Vec<Vec<i64>> = vec![vec![]; vec[0].len()];
-    for i in 0..vec[0].len() {
-        for j in 0..vec.len() {
-            transposed[i].push(vec[j][i].clone());
+fn transpose(vec: Vec<Vec<u64>>) -> Vec<Vec<u64>> {
+    let mut transposed: Vec<Vec<u64>> = vec![vec![]; vec[0].len()];
+    for row in &vec {
+        for (i, value) in row.iter().enumerate() {
+            transposed[i].push(*value);
         }
     }
     transposed
 }
 
-fn apply_operation(numbers: &Vec<Vec<i64>>, operation: &Vec<String>) -> i64 {
+fn apply_operation(numbers: &[Vec<u64>], operation: &[String]) -> Count {
     numbers
         .iter()
         .enumerate()
@@ -59,50 +71,62 @@ fn apply_operation(numbers: &Vec<Vec<i64>>, operation: &Vec<String>) -> i64 {
         .sum()
 }
 
-fn transpose_to_vector(board: Board<char>) -> Vec<Vec<i64>> {
-    let result: Vec<i64> = board
-        .rows
-        .iter()
+fn columns_to_vector(board: &Board<char>) -> Vec<Vec<u64>> {
+    // Blank columns separate the problems
+    let result: Vec<Option<u64>> = board
+        .columns()
         .map(|x| {
-            let s: String = x.iter().collect();
+            let s: String = x.collect();
             let s_n = s.trim();
-            s_n.parse::<i64>().unwrap_or(-1)
+            s_n.parse::<u64>().ok()
         })
         .collect();
 
     result
-        .split(|x| *x == -1)
-        .map(|chunk| chunk.to_vec())
+        .split(|x| x.is_none())
+        .map(|chunk| chunk.iter().flatten().copied().collect())
         .collect()
 }
 
-fn columns_to_rows(board: &mut Board<char>, operations: &Vec<String>) -> i64 {
-    board.rows.pop();
-    let transpose = board.transpose();
-    let numbers: Vec<Vec<i64>> = transpose_to_vector(transpose);
-    apply_operation(&numbers, &operations)
+fn columns_to_rows(board: &mut Board<char>, operations: &[String]) -> Result<Count> {
+    board.pop_row();
+    let numbers: Vec<Vec<u64>> = columns_to_vector(board);
+    if numbers.len() != operations.len() {
+        bail!("{} column problems for {} operations", numbers.len(), operations.len());
+    }
+    Ok(apply_operation(&numbers, operations))
 }
 
-pub fn solve() -> Result<()> {
-    let path: &str = "inputs/day06.txt";
-    let (numbers, operations) = read_file(path);
-    println!("Part 1: {}", apply_operation(&numbers, &operations));
-    let mut board = Board::read_char_board(path);
-    println!("Part 2: {}", columns_to_rows(&mut board, &operations));
-    Ok(())
+pub fn answers(input: &str) -> Result<Vec<Answer>> {
+    let (numbers, operations) = parse(input)?;
+    let part1 = apply_operation(&numbers, &operations);
+    let mut board = Board::parse(input, &|c| Ok(c), RowShape::PadWith(' '))?;
+    let part2 = columns_to_rows(&mut board, &operations)?;
+    Ok(vec![part1.into(), part2.into()])
+}
+
+pub fn solve() -> Result<[Answer; 2]> {
+    super::solve_file("inputs/day06.txt", answers)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::board::Board;
+    use crate::board::{Board, RowShape};
     use crate::days::day06::{apply_operation, read_file, columns_to_rows};
 
     #[test]
     fn test_day6() {
         let path: &str = "test/test_day06.txt";
-        let (numbers, operations) = read_file(path);
+        let (numbers, operations) = read_file(path).unwrap();
         assert_eq!(apply_operation(&numbers, &operations), 4277556);
-        let mut board = Board::read_char_board(path);
-        assert_eq!(columns_to_rows(&mut board, &operations), 3263827)
+        let mut board = Board::read_board(path, &|c| Ok(c), RowShape::PadWith(' ')).unwrap();
+        assert_eq!(columns_to_rows(&mut board, &operations).unwrap(), 3263827)
+    }
+
+    #[test]
+    fn test_day6_product_beyond_u64() {
+        let numbers = vec![vec![u64::MAX, 3]];
+        let result = apply_operation(&numbers, &["*".to_string()]);
+        assert_eq!(result.to_string(), "55340232221128654845");
     }
 }
@@ -1,60 +1,89 @@
-use crate::board::{Board, Coord};
-use anyhow::Result;
-use std::collections::{HashMap, HashSet};
+use crate::answer::Answer;
+use crate::bignum::Count;
+use crate::board::{Board, Coord, Direction};
+use anyhow::{bail, Result};
 
+// A splitter sends the beam right and left
+const SPLIT: [Coord; 2] = [Direction::Right.delta(), Direction::Left.delta()];
+
+// `visited` holds the path count from each cell, indexed like the board cells
 fn recursive_down(
     board: &Board<char>,
     coord: &Coord,
-    splitters: &mut HashSet<Coord>,
-    visited: &mut HashMap<Coord, i64>,
-) -> i64 {
-    if let Some(&value) = visited.get(coord) {
-        return value;
+    splitters: &mut Board<bool>,
+    visited: &mut [Option<Count>],
+) -> Count {
+    let Some(idx) = board.index(*coord) else {
+        return Count::from(1);
+    };
+    if let Some(value) = &visited[idx] {
+        return value.clone();
     }
 
-    let mut result = 0;
-    if let Some(value) = board.get_value(*coord) {
-        if *value == '^' {
-            splitters.insert(coord.clone());
-            result = recursive_down(board, &coord.right(), splitters, visited)
-                + recursive_down(board, &coord.left(), splitters, visited);
-        } else {
-            result = recursive_down(board, &coord.down(), splitters, visited);
-        }
+    let result = if board.cells()[idx] == '^' {
+        splitters.set_value(*coord, true);
+        coord
+            .stencil(&SPLIT)
+            .map(|side| recursive_down(board, &side, splitters, visited))
+            .sum()
     } else {
-        return 1;
-    }
-    visited.insert(*coord, result);
+        recursive_down(board, &(*coord + Direction::Down), splitters, visited)
+    };
+    visited[idx] = Some(result.clone());
     result
 }
 
-fn beam_encounters(board: &Board<char>, start_coord: &Coord) -> (i32, i64) {
-    let mut splitters: HashSet<Coord> = HashSet::new();
-    let mut visited: HashMap<Coord, i64> = HashMap::new();
-    let paths = recursive_down(board, &start_coord.down(), &mut splitters, &mut visited);
-    (splitters.len() as i32, paths)
+// The manifold, with the start cell left empty, and the start
+pub(crate) fn parse(content: &str) -> Result<(Board<char>, Coord)> {
+    let (board, markers) = Board::parse_with_markers(content, &|c| match c {
+        '^' | '.' => Ok(c),
+        _ => bail!("expected 'S', '^' or '.'"),
+    }, &['S'], '.')?;
+    match markers[&'S'].as_slice() {
+        [start_coord] => Ok((board, *start_coord)),
+        [] => bail!("No start 'S' in the manifold"),
+        starts => bail!("{} starts 'S' in the manifold", starts.len()),
+    }
 }
 
-pub fn solve() -> Result<()> {
-    let board = Board::read_char_board("inputs/day07.txt");
-    let start_coord = board.find_element('S').unwrap();
+#[cfg(test)]
+fn read_file(path: &str) -> Result<(Board<char>, Coord)> {
+    parse(&std::fs::read_to_string(path)?)
+}
+
+fn beam_encounters(board: &Board<char>, start_coord: &Coord) -> (i32, Count) {
+    let mut splitters = Board::filled(board.width(), board.height(), false);
+    let mut visited: Vec<Option<Count>> = vec![None; board.cells().len()];
+    let paths = recursive_down(board, &(*start_coord + Direction::Down), &mut splitters, &mut visited);
+    let hit = splitters.cells().iter().filter(|&&hit| hit).count();
+    (hit as i32, paths)
+}
+
+pub fn answers(input: &str) -> Result<Vec<Answer>> {
+    let (board, start_coord) = parse(input)?;
     let (part1, part2) = beam_encounters(&board, &start_coord);
-    println!("Part 1: {}", part1);
-    println!("Part 2: {}", part2);
-    Ok(())
+    Ok(vec![part1.into(), part2.into()])
+}
+
+pub fn solve() -> Result<[Answer; 2]> {
+    super::solve_file("inputs/day07.txt", answers)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::board::Board;
-    use crate::days::day07::beam_encounters;
+    use crate::days::day07::{beam_encounters, parse, read_file};
 
     #[test]
     fn test_day7() {
-        let board = Board::read_char_board("test/test_day07.txt");
-        let start_coord = board.find_element('S').unwrap();
+        let (board, start_coord) = read_file("test/test_day07.txt").unwrap();
         let (part1, part2) = beam_encounters(&board, &start_coord);
         assert_eq!(part1, 21);
         assert_eq!(part2, 40);
     }
+
+    #[test]
+    fn test_day7_needs_one_start() {
+        assert!(parse("S.S\n...\n").is_err());
+        assert!(parse("...\n.^.\n").is_err());
+    }
 }
@@ -1,29 +1,29 @@
+use crate::answer::Answer;
+use crate::board::sparse::SparseGrid;
 use crate::board::Coord;
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use std::collections::{HashSet, HashMap};
-use std::fs::File;
-use std::io::{BufRead, BufReader};
 use itertools::Itertools;
 
-fn read_file(path: &str) -> Vec<Coord> {
-    let file = File::open(path);
-    let reader = BufReader::new(file.unwrap());
-    reader
+pub(crate) fn parse(content: &str) -> Result<Vec<Coord>> {
+    let coords: Vec<Coord> = content
         .lines()
-        .map(|line| {
-            let line = line.unwrap();
-            let nums: [i32; 2] = line
-                .split(',')
-                .map(|s| s.trim().parse().unwrap())
-                .collect::<Vec<i32>>()
-                .try_into()
-                .unwrap();
-            Coord::new(nums[0], nums[1])
-        })
-        .collect()
+        .enumerate()
+        .map(|(idx, line)| line.parse().with_context(|| format!("Line {}", idx + 1)))
+        .collect::<Result<_>>()?;
+    // The red tiles close a loop, which needs at least four corners
+    if coords.len() < 4 {
+        bail!("A loop needs at least 4 red tiles, got {}", coords.len());
+    }
+    Ok(coords)
 }
 
-fn find_large_area(coords: &Vec<Coord>) -> i64 {
+#[cfg(test)]
+fn read_file(path: &str) -> Result<Vec<Coord>> {
+    parse(&std::fs::read_to_string(path)?)
+}
+
+fn find_large_area(coords: &[Coord]) -> i64 {
     coords
         .iter()
         .enumerate()
@@ -34,71 +34,47 @@ fn find_large_area(coords: &Vec<Coord>) -> i64 {
         .unwrap_or(0)
 }
 
-fn perimeter_between_coords(a: &Coord, b: &Coord) -> HashSet<Coord> {
-    if a.x == b.x {
-        let start = a.y.min(b.y);
-        let end = a.y.max(b.y);
-        (start..=end).map(|y| Coord::new(a.x, y)).collect()
+fn perimeter_between_coords(a: &Coord, b: &Coord) -> Vec<Coord> {
+    if a.x() == b.x() {
+        let start = a.y().min(b.y());
+        let end = a.y().max(b.y());
+        (start..=end).map(|y| Coord::new(a.x(), y)).collect()
     } else {
-        let start = a.x.min(b.x);
-        let end = a.x.max(b.x);
-        (start..=end).map(|x| Coord::new(x, a.y)).collect()
+        let start = a.x().min(b.x());
+        let end = a.x().max(b.x());
+        (start..=end).map(|x| Coord::new(x, a.y())).collect()
     }
 }
 
-fn calculate_perimeter(coord: &Vec<Coord>) -> HashSet<Coord> {
+fn calculate_perimeter(coord: &[Coord]) -> SparseGrid<bool> {
     // It is assumed that the coordinates are ordered to construct the perimeter.
-    let mut perimeter: HashSet<Coord> = HashSet::new();
-    for subset in coord.windows(2) {
-        let a = subset[0];
-        let b = subset[1];
-        perimeter.extend(perimeter_between_coords(&a, &b));
+    let mut perimeter: SparseGrid<bool> = SparseGrid::new(false);
+    let closing = (coord[0], coord[coord.len() - 1]);
+    for (a, b) in coord.windows(2).map(|subset| (subset[0], subset[1])).chain([closing]) {
+        for tile in perimeter_between_coords(&a, &b) {
+            perimeter.set_value(tile, true);
+        }
     }
-    perimeter.extend(perimeter_between_coords(&coord[0], &coord[coord.len() - 1]));
     perimeter
 }
 
-fn perimeter_to_hashmap(perimeter: &HashSet<Coord>) -> HashMap<i32, Vec<Coord>> {
-    let mut perimeter_y: HashMap<i32, Vec<Coord>> = HashMap::new();
-    for c in perimeter {
-        perimeter_y
-            .entry(c.y)
-            .or_default()
-            .push(*c);
-    }
-    for (_y, coords) in perimeter_y.iter_mut() {
-        coords.sort_by_key(|c| c.x);
-    }
-   perimeter_y
-}
-
 fn calculate_area(a: &Coord, b: &Coord) -> i64 {
-    let dx = (a.x as i64 - b.x as i64).abs() + 1;
-    let dy = (a.y as i64 - b.y as i64).abs() + 1;
+    let dx = (a.x() as i64 - b.x() as i64).abs() + 1;
+    let dy = (a.y() as i64 - b.y() as i64).abs() + 1;
     dx * dy
 }
 
-fn find_cross_perimeter(min: i32, max: i32, perimeter_x: &Vec<Coord>) -> bool {
-    for per in perimeter_x {
-        if (min < per.x) && (per.x < max) { return true }
-    }
-    false
-}
-
-fn is_perimeter_inside(a: &Coord, b: &Coord, perimeter_y: &HashMap<i32, Vec<Coord>>) -> bool {
-    let min_x = a.x.min(b.x);
-    let max_x = a.x.max(b.x);
-    let min_y = a.y.min(b.y);
-    let max_y = a.y.max(b.y);
-
-    for y in (min_y+1)..=(max_y-1) {
-        if let Some(x_coords) = perimeter_y.get(&y) {
-            if find_cross_perimeter(min_x, max_x, x_coords) {
-                return true;
-            }
-        }
-    }
-    false
+fn is_perimeter_inside(a: &Coord, b: &Coord, perimeter: &SparseGrid<bool>) -> bool {
+    let min_x = a.x().min(b.x());
+    let max_x = a.x().max(b.x());
+    let min_y = a.y().min(b.y());
+    let max_y = a.y().max(b.y());
+
+    ((min_y+1)..=(max_y-1)).any(|y| {
+        perimeter
+            .row(y)
+            .any(|(per, _)| (min_x < per.x()) && (per.x() < max_x))
+    })
 }
 
 fn sort_pairs(coords: &[Coord]) -> HashMap<(Coord, Coord), i64> {
@@ -118,38 +94,143 @@ fn sort_pairs(coords: &[Coord]) -> HashMap<(Coord, Coord), i64> {
     pairs.into_iter().collect()
 }
 
-fn find_green_area(coords: &Vec<Coord>) -> i64 {
+fn find_green_area(coords: &[Coord]) -> i64 {
     let perimeter = calculate_perimeter(coords);
-    let perimeter_y = perimeter_to_hashmap(&perimeter);
-    let pairs = sort_pairs(&coords);
+    let pairs = sort_pairs(coords);
     let mut result: i64 = 0;
 
     for ((a, b), area) in pairs {
-        if area > result {
-            if !is_perimeter_inside(&a, &b, &perimeter_y) {
-                result = area;
-            }
+        if area > result && !is_perimeter_inside(&a, &b, &perimeter) {
+            result = area;
         }
     }
     result
 }
 
-pub fn solve() -> Result<()> {
-    let coords = read_file("inputs/day09.txt");
-    println!("Part 1: {}", find_large_area(&coords));
-    println!("Part 2: {}", find_green_area(&coords));
+// Reference implementations, slow but easy to trust. Used by `diff-test`.
+
+fn edges(coords: &[Coord]) -> impl Iterator<Item = (Coord, Coord)> + '_ {
+    (0..coords.len()).map(|i| (coords[i], coords[(i + 1) % coords.len()]))
+}
+
+fn segments_touch(a: (Coord, Coord), b: (Coord, Coord)) -> bool {
+    let overlap = |a1: i32, a2: i32, b1: i32, b2: i32| a1.min(a2) <= b1.max(b2) && b1.min(b2) <= a1.max(a2);
+    overlap(a.0.x(), a.1.x(), b.0.x(), b.1.x()) && overlap(a.0.y(), a.1.y(), b.0.y(), b.1.y())
+}
+
+// The red tiles must describe a closed loop of axis aligned edges that never touches itself
+fn validate_polygon(coords: &[Coord]) -> Result<()> {
+    if coords.len() < 4 {
+        bail!("A loop needs at least 4 red tiles, got {}", coords.len());
+    }
+    let edge_list: Vec<(Coord, Coord)> = edges(coords).collect();
+    let n = edge_list.len();
+    for (i, &(a, b)) in edge_list.iter().enumerate() {
+        if a == b || (a.x() != b.x() && a.y() != b.y()) {
+            bail!("Tiles {:?} and {:?} are not joined by a straight line", a, b);
+        }
+        let (c, d) = edge_list[(i + 1) % n];
+        if (a.x() == b.x()) == (c.x() == d.x()) {
+            bail!("Tile {:?} does not turn a corner", b);
+        }
+        for (j, &other) in edge_list.iter().enumerate().skip(i + 2) {
+            if !(i == 0 && j == n - 1) && segments_touch((a, b), other) {
+                bail!("Edges {:?} and {:?} touch", (a, b), other);
+            }
+        }
+    }
     Ok(())
 }
 
+fn is_on_loop(tile: Coord, coords: &[Coord]) -> bool {
+    edges(coords).any(|edge| segments_touch(edge, (tile, tile)))
+}
+
+// Ray casting towards +x, counting the vertical edges crossed
+fn is_inside_loop(tile: Coord, coords: &[Coord]) -> bool {
+    edges(coords)
+        .filter(|(a, b)| {
+            a.x() == b.x() && a.x() > tile.x() && a.y().min(b.y()) <= tile.y() && tile.y() < a.y().max(b.y())
+        })
+        .count()
+        % 2
+        == 1
+}
+
+fn reference_large_area(coords: &[Coord]) -> i64 {
+    let mut result = 0;
+    for a in coords {
+        for b in coords {
+            result = result.max(calculate_area(a, b));
+        }
+    }
+    result
+}
+
+// Marks every red or green tile, then checks each rectangle tile by tile
+fn reference_green_area(coords: &[Coord]) -> i64 {
+    let coloured: HashSet<Coord> = {
+        let (min_x, max_x) = coords.iter().map(|c| c.x()).minmax().into_option().unwrap();
+        let (min_y, max_y) = coords.iter().map(|c| c.y()).minmax().into_option().unwrap();
+        (min_y..=max_y)
+            .cartesian_product(min_x..=max_x)
+            .map(|(y, x)| Coord::new(x, y))
+            .filter(|&tile| is_on_loop(tile, coords) || is_inside_loop(tile, coords))
+            .collect()
+    };
+
+    let mut result = 0;
+    for a in coords {
+        for b in coords {
+            let all_coloured = (a.y().min(b.y())..=a.y().max(b.y()))
+                .cartesian_product(a.x().min(b.x())..=a.x().max(b.x()))
+                .all(|(y, x)| coloured.contains(&Coord::new(x, y)));
+            if all_coloured {
+                result = result.max(calculate_area(a, b));
+            }
+        }
+    }
+    result
+}
+
+pub fn answers(input: &str) -> Result<Vec<Answer>> {
+    let coords = parse(input)?;
+    Ok(vec![
+        find_large_area(&coords).into(),
+        find_green_area(&coords).into(),
+    ])
+}
+
+pub fn reference_answers(input: &str) -> Result<Vec<Answer>> {
+    let coords = parse(input)?;
+    validate_polygon(&coords)?;
+    Ok(vec![
+        reference_large_area(&coords).into(),
+        reference_green_area(&coords).into(),
+    ])
+}
+
+pub fn solve() -> Result<[Answer; 2]> {
+    super::solve_file("inputs/day09.txt", answers)
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::days::day09::{find_green_area, find_large_area, read_file};
+    use crate::answer::Answer;
+    use crate::days::day09::{find_green_area, find_large_area, read_file, reference_answers};
+    use std::fs;
 
     #[test]
     fn test_day9() {
-        let coords = read_file("test/test_day09.txt");
+        let coords = read_file("test/test_day09.txt").unwrap();
         assert_eq!(find_large_area(&coords), 50);
         assert_eq!(find_green_area(&coords), 24);
     }
 
+    #[test]
+    fn test_day9_reference() {
+        let input = fs::read_to_string("test/test_day09.txt").unwrap();
+        assert_eq!(reference_answers(&input).unwrap(), vec![Answer::from(50), Answer::from(24)]);
+    }
+
 }
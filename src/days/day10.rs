@@ -1,29 +1,31 @@
-use anyhow::Result;
+use crate::answer::Answer;
+use anyhow::{anyhow, bail, Context, Result};
 use std::collections::{HashMap, HashSet, VecDeque};
-use std::fs::File;
-use std::io::{BufRead, BufReader};
 
 
+// Both solvers try every subset of buttons as a bit mask
+const MAX_BUTTONS: usize = 30;
+
 #[derive(Clone, Debug)]
-struct Machine {
+pub(crate) struct Machine {
     wiring: u32,
     raw_buttons: Vec<Vec<usize>>,
     buttons: Vec<u32>,
     joltage: Vec<u32>,
 }
 
-fn str_to_bin(s: String) -> u32 {
+fn str_to_bin(s: &str) -> Result<u32> {
     let bin_str: String = s
         .chars()
         .map(|c| match c {
-            '.' => '0',
-            '#' => '1',
-            _ => panic!("Wrong char: {}", c),
+            '.' => Ok('0'),
+            '#' => Ok('1'),
+            _ => bail!("Wrong char: {}", c),
         })
-        .collect();
-    u32::from_str_radix(&bin_str, 2).expect("Failed to parse number")
+        .collect::<Result<_>>()?;
+    u32::from_str_radix(&bin_str, 2).context("Failed to parse number")
 }
-fn vector_to_bin(vector: &Vec<usize>, n: usize) -> u32 {
+fn vector_to_bin(vector: &[usize], n: usize) -> u32 {
     let mut result = 0;
     for v in vector {
         if v < &n {
@@ -33,55 +35,75 @@ fn vector_to_bin(vector: &Vec<usize>, n: usize) -> u32 {
     }
     result
 }
-fn remove_parenthesis(s: String) -> String {
-    s.replace("[", "")
-        .replace("]", "")
-        .replace("{", "")
-        .replace("}", "")
-        .replace("(", "")
-        .replace(")", "")
+fn strip_brackets(s: &str, open: char, close: char) -> Result<&str> {
+    s.strip_prefix(open)
+        .and_then(|s| s.strip_suffix(close))
+        .ok_or_else(|| anyhow!("Expected {}...{}, found {:?}", open, close, s))
 }
 
-fn read_file(path: &str) -> Vec<Machine> {
-    let file = File::open(path);
-    let reader = BufReader::new(file.unwrap());
-    reader
+fn parse_list<T: std::str::FromStr>(s: &str) -> Result<Vec<T>> {
+    s.split(',')
+        .map(|v| v.trim().parse::<T>().map_err(|_| anyhow!("Invalid number {:?}", v)))
+        .collect()
+}
+
+pub(crate) fn parse(content: &str) -> Result<Vec<Machine>> {
+    content
         .lines()
-        .map(|l| {
-            let line = l.unwrap();
-            let mut vectors: Vec<String> = line.split(" ").map(|s| s.to_string()).collect();
-            let n = vectors[0].len() - 2;
-            let wiring = str_to_bin(remove_parenthesis(vectors[0].clone()));
-            let last_value = remove_parenthesis(vectors.pop().unwrap());
-            let joltage = last_value
-                .split(',')
-                .map(|v| v.parse::<u32>().unwrap())
-                .collect();
-            let raw_buttons: Vec<Vec<usize>> = vectors[1..]
-                .to_vec()
-                .iter()
-                .map(|s| {
-                    let clean = remove_parenthesis(s.clone());
-                    clean
-                        .split(',')
-                        .map(|v| v.parse::<usize>().unwrap())
-                        .collect()
-                })
-                .collect();
-            let buttons: Vec<u32> = raw_buttons
-                .iter()
-                .map(|vec| vector_to_bin(vec, n))
-                .collect();
-            Machine {
-                wiring,
-                raw_buttons,
-                buttons,
-                joltage,
-            }
+        .enumerate()
+        .map(|(idx, line)| {
+            let machine = parse_machine(line);
+            machine.with_context(|| format!("Line {}: {:?}", idx + 1, line))
         })
         .collect()
 }
 
+// [.##.] (3) (1,3) (2) {3,5,4,7}
+fn parse_machine(line: &str) -> Result<Machine> {
+    let vectors: Vec<&str> = line.split_whitespace().collect();
+    if vectors.len() < 2 {
+        bail!("Expected lights, buttons and joltages");
+    }
+    let lights = strip_brackets(vectors[0], '[', ']')?;
+    let n = lights.len();
+    if !(1..=32).contains(&n) {
+        bail!("Expected between 1 and 32 lights, found {}", n);
+    }
+    let wiring = str_to_bin(lights)?;
+    let joltage: Vec<u32> = parse_list(strip_brackets(vectors[vectors.len() - 1], '{', '}')?)?;
+    if joltage.len() != n {
+        bail!("Expected {} joltages, found {}", n, joltage.len());
+    }
+    let raw_buttons: Vec<Vec<usize>> = vectors[1..vectors.len() - 1]
+        .iter()
+        .map(|s| {
+            let button: Vec<usize> = parse_list(strip_brackets(s, '(', ')')?)?;
+            if let Some(light) = button.iter().find(|&&light| light >= n) {
+                bail!("Button {} refers to light {}, but there are {}", s, light, n);
+            }
+            Ok(button)
+        })
+        .collect::<Result<_>>()?;
+    if raw_buttons.len() > MAX_BUTTONS {
+        bail!("Expected at most {} buttons, found {}", MAX_BUTTONS, raw_buttons.len());
+    }
+    let buttons: Vec<u32> = raw_buttons
+        .iter()
+        .map(|vec| vector_to_bin(vec, n))
+        .collect();
+    Ok(Machine {
+        wiring,
+        raw_buttons,
+        buttons,
+        joltage,
+    })
+}
+
+#[cfg(test)]
+fn read_file(path: &str) -> Result<Vec<Machine>> {
+    parse(&std::fs::read_to_string(path)?)
+}
+
 fn press_button(button: u32, state: u32) -> u32 {
     button ^ state
 }
@@ -106,10 +128,14 @@ fn press_less_buttons(machine: &Machine, start_number: u32) -> Option<u32> {
     None
 }
 
-fn press_all_machines(machines: &Vec<Machine>) -> u32 {
+fn press_all_machines(machines: &[Machine]) -> Result<u32> {
     machines
         .iter()
-        .map(|machine| press_less_buttons(&machine, 0).unwrap())
+        .enumerate()
+        .map(|(idx, machine)| {
+            press_less_buttons(machine, 0)
+                .ok_or_else(|| anyhow!("Machine {}: no combination of buttons gives its lights", idx + 1))
+        })
         .sum()
 }
 
@@ -173,33 +199,129 @@ impl RecursiveSolver {
     }
 }
 
-fn press_joltages(machines: &Vec<Machine>) -> u64 {
+fn press_joltages(machines: &[Machine]) -> Result<u64> {
     machines
         .iter()
-        .map(|machine| {
+        .enumerate()
+        .map(|(idx, machine)| {
             let target: Vec<i64> = machine.joltage.iter().map(|&x| x as i64).collect();
             let mut solver = RecursiveSolver::new(machine.raw_buttons.clone());
-            solver.solve_recursive(target).unwrap_or(0)
+            solver
+                .solve_recursive(target)
+                .ok_or_else(|| anyhow!("Machine {}: no combination of buttons gives its joltages", idx + 1))
         })
         .sum()
 }
 
-pub fn solve() -> Result<()> {
-    let machines = read_file("inputs/day10.txt");
-    println!("Part 1: {}", press_all_machines(&machines));
-    println!("Part 2: {}", press_joltages(&machines));
-    Ok(())
+// Reference implementations, slow but easy to trust. Used by `diff-test`.
+
+// Tries every subset of buttons, since pressing a button twice undoes it
+fn reference_press_lights(machine: &Machine) -> Option<u32> {
+    (0u32..(1 << machine.buttons.len()))
+        .filter(|mask| {
+            let state = machine
+                .buttons
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| (mask >> i) & 1 == 1)
+                .fold(0, |state, (_, &button)| press_button(button, state));
+            state == machine.wiring
+        })
+        .map(|mask| mask.count_ones())
+        .min()
+}
+
+// Tries every number of presses of each button in turn
+fn reference_press_joltage(
+    buttons: &[Vec<usize>],
+    remaining: Vec<i64>,
+    memo: &mut HashMap<(usize, Vec<i64>), Option<u64>>,
+) -> Option<u64> {
+    let Some(button) = buttons.first() else {
+        return remaining.iter().all(|&x| x == 0).then_some(0);
+    };
+    let key = (buttons.len(), remaining.clone());
+    if let Some(&res) = memo.get(&key) {
+        return res;
+    }
+
+    let max_presses = button.iter().map(|&idx| remaining[idx]).min().unwrap_or(0);
+    let mut best: Option<u64> = None;
+    for presses in 0..=max_presses {
+        let mut next = remaining.clone();
+        for &idx in button {
+            next[idx] -= presses;
+        }
+        if let Some(rest) = reference_press_joltage(&buttons[1..], next, memo) {
+            let total = presses as u64 + rest;
+            best = Some(best.map_or(total, |b| b.min(total)));
+        }
+    }
+    memo.insert(key, best);
+    best
+}
+
+pub fn answers(input: &str) -> Result<Vec<Answer>> {
+    let machines = parse(input)?;
+    Ok(vec![
+        press_all_machines(&machines)?.into(),
+        press_joltages(&machines)?.into(),
+    ])
+}
+
+pub fn reference_answers(input: &str) -> Result<Vec<Answer>> {
+    let machines = parse(input)?;
+    let mut part1 = 0;
+    let mut part2 = 0;
+    for machine in &machines {
+        part1 += reference_press_lights(machine)
+            .ok_or_else(|| anyhow!("Lights can not be matched: {:?}", machine))?;
+        let target: Vec<i64> = machine.joltage.iter().map(|&x| x as i64).collect();
+        part2 += reference_press_joltage(&machine.raw_buttons, target, &mut HashMap::new())
+            .ok_or_else(|| anyhow!("Joltage can not be matched: {:?}", machine))?;
+    }
+    Ok(vec![part1.into(), part2.into()])
+}
+
+pub fn solve() -> Result<[Answer; 2]> {
+    super::solve_file("inputs/day10.txt", answers)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
 
     #[test]
     fn test_day10() {
-        let machines = read_file("test/test_day10.txt");
-        assert_eq!(press_all_machines(&machines), 7);
-        assert_eq!(press_joltages(&machines), 33);
+        let machines = read_file("test/test_day10.txt").unwrap();
+        assert_eq!(press_all_machines(&machines).unwrap(), 7);
+        assert_eq!(press_joltages(&machines).unwrap(), 33);
+    }
+
+    #[test]
+    fn test_day10_reference() {
+        let input = fs::read_to_string("test/test_day10.txt").unwrap();
+        assert_eq!(reference_answers(&input).unwrap(), vec![Answer::from(7), Answer::from(33)]);
+    }
+
+    #[test]
+    fn test_day10_unreachable_lights() {
+        let error = answers("[..] (0,1) {1,1}\n[.#] (0) {1,0}\n").unwrap_err();
+        assert_eq!(error.to_string(), "Machine 2: no combination of buttons gives its lights");
+    }
+
+    #[test]
+    fn test_day10_unreachable_joltages() {
+        let error = answers("[##] (0,1) {1,1}\n[#.] (0) {1,1}\n").unwrap_err();
+        assert_eq!(error.to_string(), "Machine 2: no combination of buttons gives its joltages");
+    }
+
+    #[test]
+    fn test_day10_too_many_buttons() {
+        let buttons = " (0)".repeat(MAX_BUTTONS + 1);
+        assert!(parse(&format!("[#]{} {{1}}", buttons)).is_err());
+        assert!(parse(&format!("[#]{} {{1}}", &buttons[4..])).is_ok());
     }
 
 }
@@ -1,12 +1,12 @@
-use anyhow::Result;
+use crate::answer::Answer;
+use crate::bignum::Count;
+use anyhow::{bail, Result};
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
 
 struct Graph {
     adjacency: HashMap<String, Vec<String>>,
-    cache: HashMap<String, u64>,
-    cond_cache: HashMap<(String, u8), u64>,
+    cache: HashMap<String, Count>,
+    cond_cache: HashMap<(String, u8), Count>,
 }
 
 impl Graph {
@@ -18,26 +18,26 @@ impl Graph {
         }
     }
 
-    fn count_paths(&mut self, current: String, end: String) -> u64 {
+    fn count_paths(&mut self, current: String, end: String) -> Count {
         if current == end {
-            return 1;
+            return Count::from(1);
         }
 
-        if let Some(&count) = self.cache.get(&current) {
-            return count;
+        if let Some(count) = self.cache.get(&current) {
+            return count.clone();
         }
 
-        let mut count = 0;
+        let mut count = Count::default();
         if let Some(neighbors) = self.adjacency.get(&current).cloned() {
             for neighbor in neighbors {
-                count += self.count_paths(neighbor, end.clone());
+                count = count + self.count_paths(neighbor, end.clone());
             }
         }
-        self.cache.insert(current, count);
+        self.cache.insert(current, count.clone());
         count
     }
 
-    fn count_paths_with_conditions(&mut self, current: String, end: String, mask: u8) -> u64 {
+    fn count_paths_with_conditions(&mut self, current: String, end: String, mask: u8) -> Count {
         self.cache.clear();
         let mut new_mask = mask;
         if current == "dac" {
@@ -47,54 +47,91 @@ impl Graph {
         }
 
         if current == end {
-            return if new_mask == 3 { 1 } else { 0 };
+            return Count::from(if new_mask == 3 { 1 } else { 0 });
         }
 
         let state_key = (current.clone(), new_mask);
-        if let Some(&count) = self.cond_cache.get(&state_key) {
-            return count;
+        if let Some(count) = self.cond_cache.get(&state_key) {
+            return count.clone();
         }
 
-        let mut count = 0;
+        let mut count = Count::default();
         if let Some(neighbors) = self.adjacency.get(&current).cloned() {
             for neighbor in neighbors {
-                count += self.count_paths_with_conditions(neighbor, end.clone(), new_mask);
+                count = count + self.count_paths_with_conditions(neighbor, end.clone(), new_mask);
             }
         }
-        self.cond_cache.insert(state_key, count);
+        self.cond_cache.insert(state_key, count.clone());
         count
     }
 }
 
-fn read_file(path: &str) -> HashMap<String, Vec<String>> {
-    let file = File::open(path).unwrap();
-    let reader = BufReader::new(file);
+pub(crate) fn parse(content: &str) -> Result<HashMap<String, Vec<String>>> {
     let mut map: HashMap<String, Vec<String>> = HashMap::new();
-    reader.lines().for_each(|line| {
-        // CAMBIO: map -> for_each
-        let line = line.unwrap();
-        let parts: Vec<&str> = line.split(":").collect();
-        let key = parts[0].trim().to_string();
-        let raw = parts[1].trim().to_string();
+    for (idx, line) in content.lines().enumerate() {
+        let Some((key, raw)) = line.split_once(":") else {
+            bail!("Line {}: expected 'device: outputs', found {:?}", idx + 1, line);
+        };
+        let key = key.trim().to_string();
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            bail!("Line {}: invalid device name {:?}", idx + 1, key);
+        }
         let value: Vec<String> = raw.split_whitespace().map(|s| s.to_string()).collect();
+        if value.iter().any(|v| v.contains(':')) {
+            bail!("Line {}: more than one ':'", idx + 1);
+        }
+
+        if map.insert(key.clone(), value).is_some() {
+            bail!("Line {}: device {} is listed twice", idx + 1, key);
+        }
+    }
+    if let Some(device) = find_cycle(&map) {
+        bail!("Device {} feeds back into itself", device);
+    }
+    Ok(map)
+}
+
+// Path counting recurses along the outputs, so the devices must form a DAG.
+// Peels off devices with no remaining inputs; whatever is left lies on a cycle.
+fn find_cycle(map: &HashMap<String, Vec<String>>) -> Option<&String> {
+    let mut inputs: HashMap<&String, usize> = map.keys().map(|key| (key, 0)).collect();
+    for output in map.values().flatten() {
+        if let Some(count) = inputs.get_mut(output) {
+            *count += 1;
+        }
+    }
+    let mut ready: Vec<&String> = inputs.iter().filter(|(_, &n)| n == 0).map(|(&key, _)| key).collect();
+    while let Some(device) = ready.pop() {
+        inputs.remove(device);
+        for output in &map[device] {
+            if let Some(count) = inputs.get_mut(output) {
+                *count -= 1;
+                if *count == 0 {
+                    ready.push(output);
+                }
+            }
+        }
+    }
+    inputs.into_keys().min()
+}
+
+#[cfg(test)]
+fn read_file(path: &str) -> Result<HashMap<String, Vec<String>>> {
+    parse(&std::fs::read_to_string(path)?)
+}
 
-        map.insert(key, value);
-    });
-    map
+pub fn answers(input: &str) -> Result<Vec<Answer>> {
+    let mut graph = Graph::new(parse(input)?);
+    Ok(vec![
+        graph.count_paths("you".to_string(), "out".to_string()).into(),
+        graph
+            .count_paths_with_conditions("svr".to_string(), "out".to_string(), 0)
+            .into(),
+    ])
 }
 
-pub fn solve() -> Result<()> {
-    let map: HashMap<String, Vec<String>> = read_file("inputs/day11.txt");
-    let mut graph = Graph::new(map);
-    println!(
-        "Part 1: {}",
-        graph.count_paths("you".to_string(), "out".to_string())
-    );
-    println!(
-        "Part 2: {}",
-        graph.count_paths_with_conditions("svr".to_string(), "out".to_string(), 0)
-    );
-    Ok(())
+pub fn solve() -> Result<[Answer; 2]> {
+    super::solve_file("inputs/day11.txt", answers)
 }
 
 #[cfg(test)]
@@ -104,15 +141,33 @@ mod tests {
 
     #[test]
     fn test_day11() {
-        let map: HashMap<String, Vec<String>> = read_file("test/test_day11.txt");
+        let map: HashMap<String, Vec<String>> = read_file("test/test_day11.txt").unwrap();
         let mut graph = Graph::new(map);
         assert_eq!(graph.count_paths("you".to_string(), "out".to_string()), 5);
 
-        let map2: HashMap<String, Vec<String>> = read_file("test/test_day11-b.txt");
+        let map2: HashMap<String, Vec<String>> = read_file("test/test_day11-b.txt").unwrap();
         let mut graph2 = Graph::new(map2);
         assert_eq!(
             graph2.count_paths_with_conditions("svr".to_string(), "out".to_string(), 0),
             2
         );
     }
+
+    #[test]
+    fn test_day11_count_beyond_u64() {
+        // 70 diamonds in a row double the number of paths each time
+        let mut map: HashMap<String, Vec<String>> = HashMap::new();
+        for i in 0..70 {
+            let next = if i == 69 { "out".to_string() } else { format!("n{}", i + 1) };
+            let name = if i == 0 { "you".to_string() } else { format!("n{}", i) };
+            map.insert(name, vec![format!("a{}", i), format!("b{}", i)]);
+            map.insert(format!("a{}", i), vec![next.clone()]);
+            map.insert(format!("b{}", i), vec![next]);
+        }
+        let mut graph = Graph::new(map);
+        assert_eq!(
+            graph.count_paths("you".to_string(), "out".to_string()).to_string(),
+            "1180591620717411303424"
+        );
+    }
 }
@@ -1,100 +1,284 @@
+use crate::answer::Answer;
 use crate::board::Board;
-use anyhow::Result;
+use anyhow::{anyhow, bail, Context, Result};
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
 
-fn read_file(path: &str) -> (HashMap<usize, Board<char>>, Vec<(usize, usize, Vec<usize>)>) {
-    let file = File::open(path).unwrap();
-    let reader = BufReader::new(file);
+// Region width, height and the number of presents of each shape
+type Instruction = (usize, usize, Vec<usize>);
 
+// Present shapes by ID and the regions to fill
+type Puzzle = (HashMap<usize, Board<char>>, Vec<Instruction>);
+
+pub(crate) fn parse(content: &str) -> Result<Puzzle> {
     let mut boards = HashMap::new();
     let mut instructions = Vec::new();
 
     let mut current_id: Option<usize> = None;
     let mut current_rows: Vec<Vec<char>> = Vec::new();
 
-    for line_result in reader.lines() {
-        let line = line_result.unwrap();
+    let mut save_board = |id: usize, rows: Vec<Vec<char>>| {
+        if rows.is_empty() {
+            bail!("Shape {} has no rows", id);
+        }
+        if boards.insert(id, Board::from_rows(rows)?).is_some() {
+            bail!("Shape {} is defined twice", id);
+        }
+        Ok(())
+    };
+
+    for (idx, line) in content.lines().enumerate() {
         let trim_line = line.trim();
 
         if trim_line.is_empty() {
-            if let Some(id) = current_id {
-                boards.insert(id, Board::new(current_rows.clone()));
-                current_rows.clear();
-                current_id = None;
+            if let Some(id) = current_id.take() {
+                save_board(id, std::mem::take(&mut current_rows))?;
             }
             continue;
         }
 
-        // Detectar cabecera de bloque (ej: "0:") vs instrucción (ej: "4x4:")
-        if trim_line.ends_with(':') && !trim_line.contains('x') {
+        // Detectar cabecera de bloque (ej: "0:") vs instrucción (ej: "4x4: 1 2")
+        if let Some((head, tail)) = trim_line.split_once(':') {
             // Guardamos el anterior si quedó pendiente
-            if let Some(id) = current_id {
-                boards.insert(id, Board::new(current_rows.clone()));
-                current_rows.clear();
+            if let Some(id) = current_id.take() {
+                save_board(id, std::mem::take(&mut current_rows))?;
             }
 
-            let id_str = &trim_line[..trim_line.len() - 1];
-            current_id = Some(id_str.parse().expect("ID inválido"));
-        } else if trim_line.contains(':') {
-            if let Some(id) = current_id {
-                boards.insert(id, Board::new(current_rows.clone()));
-                current_rows.clear();
-                current_id = None;
+            if tail.trim().is_empty() && !head.contains('x') {
+                let id = head.trim().parse().with_context(|| format!("Line {}: invalid shape ID {:?}", idx + 1, head))?;
+                current_id = Some(id);
+                continue;
             }
 
-            let parts: Vec<&str> = trim_line.split(':').collect();
-            let dims_str = parts[0].trim();
-            let nums_str = parts[1].trim();
+            let (width, height) = head
+                .split_once('x')
+                .ok_or_else(|| anyhow!("Line {}: expected WxH, found {:?}", idx + 1, head))?;
+            let width = width.trim().parse().with_context(|| format!("Line {}: invalid width", idx + 1))?;
+            let height = height.trim().parse().with_context(|| format!("Line {}: invalid height", idx + 1))?;
 
-            let dims: Vec<&str> = dims_str.split('x').collect();
-            let width = dims[0].parse().unwrap();
-            let height = dims[1].parse().unwrap();
-
-            let sequence: Vec<usize> = nums_str
+            let sequence: Vec<usize> = tail
                 .split_whitespace()
-                .map(|s| s.parse().unwrap())
-                .collect();
+                .map(|s| s.parse().with_context(|| format!("Line {}: invalid count {:?}", idx + 1, s)))
+                .collect::<Result<_>>()?;
 
             instructions.push((width, height, sequence));
-        } else {
-            if current_id.is_some() {
-                current_rows.push(trim_line.chars().collect());
+        } else if current_id.is_some() {
+            if let Some(c) = trim_line.chars().find(|&c| c != '#' && c != '.') {
+                bail!("Line {}: unexpected {:?} in a shape", idx + 1, c);
             }
+            current_rows.push(trim_line.chars().collect());
+        } else {
+            bail!("Line {}: shape row outside of a shape: {:?}", idx + 1, trim_line);
         }
     }
 
     if let Some(id) = current_id {
-        boards.insert(id, Board::new(current_rows));
+        save_board(id, current_rows)?;
     }
 
-    (boards, instructions)
+    if let Some(id) = (0..boards.len()).find(|id| !boards.contains_key(id)) {
+        bail!("Shape IDs must be 0 to {}, missing {}", boards.len() - 1, id);
+    }
+    if let Some((w, h, _)) = instructions.iter().find(|(_, _, counts)| counts.len() > boards.len()) {
+        bail!("Region {}x{} lists more shapes than the {} defined", w, h, boards.len());
+    }
+
+    Ok((boards, instructions))
+}
+
+fn region_area(w: usize, h: usize) -> Result<usize> {
+    w.checked_mul(h).ok_or_else(|| anyhow!("Region {}x{} is too large", w, h))
+}
+
+// Cells covered by `counts` presents of each shape, given the cells of each shape
+fn presents_area(cells: &[usize], counts: &[usize], (w, h): (usize, usize)) -> Result<usize> {
+    counts
+        .iter()
+        .zip(cells)
+        .try_fold(0usize, |total, (&count, &shape_cells)| {
+            count.checked_mul(shape_cells).and_then(|area| total.checked_add(area))
+        })
+        .ok_or_else(|| anyhow!("Presents for region {}x{} cover too many cells", w, h))
 }
 
 // When checking whether an entry is possible, the number of boards that is a solution is obtained. A little trick
 fn check_fit(
     boards: &HashMap<usize, Board<char>>,
-    instructions: &Vec<(usize, usize, Vec<usize>)>,
-) -> u32 {
+    instructions: &[Instruction],
+) -> Result<u32> {
+    let cells: Vec<usize> = (0..boards.len()).map(|idx| boards[&idx].count_values('#')).collect();
     let mut count = 0;
     for (w, h, vec) in instructions {
-        let area = w * h;
-        let board_area: usize = vec
-            .iter()
-            .enumerate()
-            .map(|(idx, v)| boards.get(&idx).unwrap().count_values('#') * v)
-            .sum();
-        println!("{:?} {:?}", area, board_area);
+        let area = region_area(*w, *h)?;
+        let board_area = presents_area(&cells, vec, (*w, *h))?;
         if board_area < area {
             count += 1;
         }
     }
-    count
+    Ok(count)
+}
+
+// Reference implementation, slow but easy to trust. Used by `diff-test`.
+
+// Every rotation and reflection of a shape, as offsets from its first cell in reading order
+fn orientations(board: &Board<char>) -> Vec<Vec<(i32, i32)>> {
+    let mut result: Vec<Vec<(i32, i32)>> = Vec::new();
+    for oriented in board.orientations() {
+        let cells: Vec<(i32, i32)> = oriented
+            .rows()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|&(_, &c)| c == '#')
+                    .map(move |(x, _)| (x as i32, y as i32))
+            })
+            .collect();
+        let Some(&(ax, ay)) = cells.first() else {
+            continue;
+        };
+        let normalized: Vec<(i32, i32)> = cells.iter().map(|&(x, y)| (x - ax, y - ay)).collect();
+        if !result.contains(&normalized) {
+            result.push(normalized);
+        }
+    }
+    result
 }
 
-pub fn solve() -> Result<()> {
-    let (boards, instructions) = read_file("inputs/day12.txt");
-    println!("Part 1: {}", check_fit(&boards, &instructions));
-    Ok(())
+// Walks the cells in reading order; each free cell is either left empty or becomes
+// the first cell of a present.
+fn reference_pack(
+    (width, height): (usize, usize),
+    shapes: &[Vec<Vec<(i32, i32)>>],
+    counts: &mut [usize],
+    grid: &mut [bool],
+    pos: usize,
+    free: usize,
+    needed: usize,
+) -> bool {
+    if needed == 0 {
+        return true;
+    }
+    if free < needed || pos == grid.len() {
+        return false;
+    }
+    if grid[pos] {
+        return reference_pack((width, height), shapes, counts, grid, pos + 1, free, needed);
+    }
+
+    let (x, y) = ((pos % width) as i32, (pos / width) as i32);
+    for shape in 0..shapes.len() {
+        if counts[shape] == 0 {
+            continue;
+        }
+        for orientation in &shapes[shape] {
+            let cells: Option<Vec<usize>> = orientation
+                .iter()
+                .map(|&(dx, dy)| {
+                    let (cx, cy) = (x + dx, y + dy);
+                    if cx < 0 || cy < 0 || cx as usize >= width || cy as usize >= height {
+                        return None;
+                    }
+                    let idx = cy as usize * width + cx as usize;
+                    (!grid[idx]).then_some(idx)
+                })
+                .collect();
+            let Some(cells) = cells else { continue };
+
+            cells.iter().for_each(|&idx| grid[idx] = true);
+            counts[shape] -= 1;
+            let fits = reference_pack(
+                (width, height),
+                shapes,
+                counts,
+                grid,
+                pos + 1,
+                free - cells.len(),
+                needed - cells.len(),
+            );
+            counts[shape] += 1;
+            cells.iter().for_each(|&idx| grid[idx] = false);
+            if fits {
+                return true;
+            }
+        }
+    }
+    reference_pack((width, height), shapes, counts, grid, pos + 1, free - 1, needed)
+}
+
+fn reference_check_fit(
+    boards: &HashMap<usize, Board<char>>,
+    instructions: &[Instruction],
+) -> Result<u32> {
+    let shapes: Vec<Vec<Vec<(i32, i32)>>> = (0..boards.len())
+        .map(|idx| {
+            let board = boards.get(&idx).ok_or_else(|| anyhow!("Missing shape {}", idx))?;
+            if board.count_values('#') == 0 {
+                bail!("Shape {} is empty", idx);
+            }
+            Ok(orientations(board))
+        })
+        .collect::<Result<_>>()?;
+    let cells: Vec<usize> = shapes.iter().map(|shape| shape[0].len()).collect();
+
+    let mut count = 0;
+    for (w, h, vec) in instructions {
+        if vec.len() > shapes.len() {
+            bail!("Region {}x{} refers to unknown shapes", w, h);
+        }
+        let mut counts = vec.clone();
+        let area = region_area(*w, *h)?;
+        let needed = presents_area(&cells, vec, (*w, *h))?;
+        let mut grid = vec![false; area];
+        if reference_pack((*w, *h), &shapes, &mut counts, &mut grid, 0, area, needed) {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+pub fn answers(input: &str) -> Result<Vec<Answer>> {
+    let (boards, instructions) = parse(input)?;
+    Ok(vec![check_fit(&boards, &instructions)?.into()])
+}
+
+pub fn reference_answers(input: &str) -> Result<Vec<Answer>> {
+    let (boards, instructions) = parse(input)?;
+    Ok(vec![reference_check_fit(&boards, &instructions)?.into()])
+}
+
+pub fn solve() -> Result<[Answer; 2]> {
+    super::solve_file("inputs/day12.txt", answers)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answer::Answer;
+    use crate::days::day12::{answers, parse, reference_answers};
+    use std::fs;
+
+    #[test]
+    fn test_day12_reference() {
+        let input = fs::read_to_string("test/test_day12.txt").unwrap();
+        // The last region does not fit, which takes the reference a long search to prove
+        let feasible: Vec<&str> = input.trim_end().lines().collect();
+        let feasible = feasible[..feasible.len() - 1].join("\n");
+        assert_eq!(reference_answers(&feasible).unwrap(), vec![Answer::from(2)]);
+    }
+
+    #[test]
+    fn test_day12_shapes_without_blank_lines() {
+        // A header closes the shape before it, and shapes can differ in size
+        let (shapes, regions) = parse("0:\n###\n#..\n###\n1:\n##\n##\n\n3x3: 1 0\n").unwrap();
+        assert_eq!((shapes[&0].height(), shapes[&1].height()), (3, 2));
+        assert_eq!(regions, vec![(3, 3, vec![1, 0])]);
+    }
+
+    #[test]
+    fn test_day12_huge_regions() {
+        let shape = "0:\n##\n##\n\n";
+        let error = answers(&format!("{}{}x{}: 1\n", shape, usize::MAX, 2)).unwrap_err();
+        assert_eq!(error.to_string(), format!("Region {}x2 is too large", usize::MAX));
+        assert!(answers(&format!("{}2x2: {}\n", shape, usize::MAX)).is_err());
+        assert!(reference_answers(&format!("{}2x2: {}\n", shape, usize::MAX)).is_err());
+    }
 }
@@ -9,4 +9,133 @@ pub mod day08;
 pub mod day09;
 pub mod day10;
 pub mod day11;
-pub mod day12;
\ No newline at end of file
+pub mod day12;
+
+use crate::answer::Answer;
+use anyhow::{bail, Result};
+use std::fs;
+
+// Parses an input for a day without solving it
+pub fn parse_input(day: u8, input: &str) -> Result<()> {
+    match day {
+        1 => day01::parse(input).map(drop),
+        2 => day02::parse(input).map(drop),
+        3 => day03::parse(input).map(drop),
+        4 => day04::parse(input).map(drop),
+        5 => day05::parse(input).map(drop),
+        6 => day06::parse(input).map(drop),
+        7 => day07::parse(input).map(drop),
+        8 => day08::parse(input).map(drop),
+        9 => day09::parse(input).map(drop),
+        10 => day10::parse(input).map(drop),
+        11 => day11::parse(input).map(drop),
+        12 => day12::parse(input).map(drop),
+        _ => bail!("No parser for day {}", day),
+    }
+}
+
+// Solves an input for a day, both parts
+pub fn answers(day: u8, input: &str) -> Result<Vec<Answer>> {
+    match day {
+        1 => day01::answers(input),
+        2 => day02::answers(input),
+        3 => day03::answers(input),
+        4 => day04::answers(input),
+        5 => day05::answers(input),
+        6 => day06::answers(input),
+        7 => day07::answers(input),
+        8 => day08::answers(input),
+        9 => day09::answers(input),
+        10 => day10::answers(input),
+        11 => day11::answers(input),
+        12 => day12::answers(input),
+        _ => bail!("No solver for day {}", day),
+    }
+}
+
+// Solves a day's input file with its `answers`, marking missing parts as unsolved
+fn solve_file(path: &str, answers: fn(&str) -> Result<Vec<Answer>>) -> Result<[Answer; 2]> {
+    let mut parts = answers(&fs::read_to_string(path)?)?.into_iter();
+    Ok([
+        parts.next().unwrap_or(Answer::Unsolved),
+        parts.next().unwrap_or(Answer::Unsolved),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answer::Answer;
+    use crate::days::{answers, day03, day12, parse_input, solve_file};
+    use crate::generator::{generate, Rng};
+    use std::fs;
+    use std::panic::{self, AssertUnwindSafe};
+
+    const DAYS: [u8; 12] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+    const GARBAGE: [&str; 14] = ["#", ".", ",", ":", "-", "x", "[", "}", "(", " ", "\n", "\r", "9", "é"];
+
+    fn mutate(rng: &mut Rng, input: &str) -> String {
+        let mut chars: Vec<char> = input.chars().collect();
+        let mut lines: Vec<String> = input.lines().map(|s| s.to_string()).collect();
+        match rng.below(5) {
+            0 => chars.truncate(rng.below(chars.len() as u64 + 1) as usize),
+            1 if lines.len() > 1 => {
+                rng.shuffle(&mut lines);
+                return lines.join("\n");
+            }
+            2 if !lines.is_empty() => {
+                let line = lines[rng.below(lines.len() as u64) as usize].clone();
+                lines.insert(rng.below(lines.len() as u64 + 1) as usize, line);
+                return lines.join("\n");
+            }
+            3 if !chars.is_empty() => {
+                chars.remove(rng.below(chars.len() as u64) as usize);
+            }
+            _ => {
+                let pos = rng.below(chars.len() as u64 + 1) as usize;
+                for (i, c) in rng.choose(&GARBAGE).chars().enumerate() {
+                    chars.insert(pos + i, c);
+                }
+            }
+        }
+        chars.into_iter().collect()
+    }
+
+    #[test]
+    fn test_solve_file_validates_and_pads() {
+        // Rows shorter than twelve digits are rejected by day03's parser
+        assert!(solve_file("test/malformed/day03-short-banks.txt", day03::answers).is_err());
+        let [_, second] = solve_file("test/test_day12.txt", day12::answers).unwrap();
+        assert_eq!(second, Answer::Unsolved);
+    }
+
+    #[test]
+    fn test_malformed_corpus_is_rejected() {
+        for entry in fs::read_dir("test/malformed").unwrap() {
+            let path = entry.unwrap().path();
+            let name = path.file_name().unwrap().to_string_lossy().to_string();
+            let day: u8 = name[3..5].parse().unwrap();
+            let input = fs::read_to_string(&path).unwrap();
+            let result = panic::catch_unwind(|| parse_input(day, &input).is_err() || answers(day, &input).is_err());
+            assert!(result.unwrap(), "{} was accepted", name);
+        }
+    }
+
+    #[test]
+    fn test_mutated_inputs_do_not_panic() {
+        let mut rng = Rng::new(2025);
+        for day in DAYS {
+            let valid = generate(day, 6, day as u64).unwrap();
+            assert!(parse_input(day, &valid).is_ok());
+            for _ in 0..300 {
+                let mut input = valid.clone();
+                for _ in 0..rng.between(1, 3) {
+                    input = mutate(&mut rng, &input);
+                }
+                let result = panic::catch_unwind(AssertUnwindSafe(|| parse_input(day, &input)));
+                assert!(result.is_ok(), "Day {} parser panicked on {:?}", day, input);
+                let result = panic::catch_unwind(AssertUnwindSafe(|| answers(day, &input)));
+                assert!(result.is_ok(), "Day {} solver panicked on {:?}", day, input);
+            }
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use crate::answer::Answer;
+use crate::days;
+use crate::generator;
+use anyhow::{bail, Result};
+use std::any::Any;
+use std::ops::Range;
+use std::panic::{self, AssertUnwindSafe};
+use std::thread;
+
+type Answers = fn(&str) -> Result<Vec<Answer>>;
+
+#[derive(Debug)]
+pub struct Mismatch {
+    pub seed: u64,
+    pub input: String,
+    pub part: usize,
+    pub fast: Answer,
+    pub reference: Answer,
+}
+
+fn solvers(day: u8) -> Result<(Answers, Answers)> {
+    match day {
+        4 => Ok((days::day04::answers, days::day04::reference_answers)),
+        9 => Ok((days::day09::answers, days::day09::reference_answers)),
+        10 => Ok((days::day10::answers, days::day10::reference_answers)),
+        12 => Ok((days::day12::answers, days::day12::reference_answers)),
+        _ => bail!("No reference implementation for day {}", day),
+    }
+}
+
+// What a panic was raised with, for reporting it as an answer
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_default()
+}
+
+// A panic in a solver is caught here and counts as an answer, so that crashing
+// solvers are reported like wrong ones. Panics anywhere else still propagate.
+fn run_catching(answers: Answers, input: &str) -> thread::Result<Result<Vec<Answer>>> {
+    panic::catch_unwind(AssertUnwindSafe(|| answers(input)))
+}
+
+// First part where both implementations disagree, or None when the input is not
+// valid for the reference implementation.
+fn first_difference(day: u8, input: &str) -> Option<(usize, Answer, Answer)> {
+    let (fast, reference) = solvers(day).ok()?;
+    let expected = run_catching(reference, input).ok()?.ok()?;
+    let actual = match run_catching(fast, input) {
+        Ok(Ok(answers)) => answers,
+        Ok(Err(e)) => vec![Answer::from(format!("error: {}", e))],
+        Err(payload) => vec![Answer::from(format!("panicked: {}", panic_message(&*payload)))],
+    };
+    (0..expected.len())
+        .find(|&i| actual.get(i) != expected.get(i))
+        .map(|i| {
+            let fast = actual.get(i).or(actual.last()).cloned().unwrap_or(Answer::Unsolved);
+            (i + 1, fast, expected[i].clone())
+        })
+}
+
+// Lines that are kept or dropped together: a header ending in ':' with the lines
+// after it up to and including the next blank line, so a day12 shape is never cut
+// in half. Every other line stands alone.
+fn units<'a>(lines: &[&'a str]) -> Vec<Vec<&'a str>> {
+    let mut units: Vec<Vec<&str>> = Vec::new();
+    let mut in_block = false;
+    for &line in lines {
+        let trimmed = line.trim();
+        if trimmed.contains(':') {
+            in_block = trimmed.ends_with(':');
+            units.push(vec![line]);
+        } else if in_block {
+            units.last_mut().unwrap().push(line);
+            in_block = !trimmed.is_empty();
+        } else {
+            units.push(vec![line]);
+        }
+    }
+    units
+}
+
+// Pairs of neighbouring units first, then single units
+fn removals(count: usize) -> Vec<Range<usize>> {
+    [2, 1]
+        .iter()
+        .flat_map(|&chunk| (0..count.saturating_sub(chunk - 1)).map(move |i| i..i + chunk))
+        .collect()
+}
+
+// A shrunk input only counts if it is still valid for the day and still shows the bug
+fn still_disagrees(day: u8, input: &str) -> bool {
+    days::parse_input(day, input).is_ok() && first_difference(day, input).is_some()
+}
+
+// Drops units for as long as the implementations keep disagreeing
+fn minimize(day: u8, input: &str) -> String {
+    let lines: Vec<&str> = input.lines().collect();
+    let mut units = units(&lines);
+    let text = |units: &[Vec<&str>]| units.concat().join("\n") + "\n";
+    'shrink: loop {
+        for range in removals(units.len()) {
+            let mut candidate = units.clone();
+            candidate.drain(range);
+            if still_disagrees(day, &text(&candidate)) {
+                units = candidate;
+                continue 'shrink;
+            }
+        }
+        return text(&units);
+    }
+}
+
+pub fn run(day: u8, cases: usize, max_size: usize, seed: u64) -> Result<Option<Mismatch>> {
+    solvers(day)?;
+    let mut mismatch = None;
+    for case in 0..cases {
+        let case_seed = seed.wrapping_add(case as u64);
+        let input = generator::generate(day, 1 + case % max_size.max(1), case_seed)?;
+        if first_difference(day, &input).is_some() {
+            let input = minimize(day, &input);
+            let (part, fast, reference) = first_difference(day, &input).unwrap();
+            mismatch = Some(Mismatch {
+                seed: case_seed,
+                input,
+                part,
+                fast,
+                reference,
+            });
+            break;
+        }
+    }
+
+    Ok(mismatch)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::parse_input;
+    use crate::difftest::{first_difference, minimize, run, units};
+
+    #[test]
+    fn test_day10_agrees_with_reference() {
+        assert!(run(10, 20, 4, 0).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_minimize_keeps_disagreement() {
+        // Regions filled exactly are rejected by the area check
+        let input = "0:\n###\n###\n###\n\n4x4: 1\n6x6: 4\n3x3: 1\n";
+        assert!(first_difference(12, input).is_some());
+        let minimized = minimize(12, input);
+        assert!(first_difference(12, &minimized).is_some());
+        assert!(minimized.lines().count() < input.lines().count());
+    }
+
+    #[test]
+    fn test_minimize_keeps_shapes_whole() {
+        let input = "0:\n###\n###\n###\n\n1:\n#..\n##.\n###\n\n4x4: 1 0\n6x6: 4 0\n3x3: 1 0\n5x5: 0 1\n";
+        assert!(first_difference(12, input).is_some());
+        let minimized = minimize(12, input);
+        assert!(parse_input(12, &minimized).is_ok());
+        assert!(first_difference(12, &minimized).is_some());
+        assert!(minimized.contains("0:\n###\n###\n###\n\n"));
+
+        // Shapes without blank lines between them stay whole too
+        let lines = ["0:", "###", "#..", "###", "1:", "##", "##", "", "3x3: 1 0"];
+        let grouped: Vec<usize> = units(&lines).iter().map(|unit| unit.len()).collect();
+        assert_eq!(grouped, vec![4, 4, 1]);
+    }
+}
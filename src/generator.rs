@@ -0,0 +1,418 @@
+use anyhow::{bail, Result};
+use std::collections::HashSet;
+
+// SplitMix64, small and good enough to make reproducible puzzle inputs
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // Uniform value in [0, n)
+    pub fn below(&mut self, n: u64) -> u64 {
+        if n == 0 {
+            return 0;
+        }
+        self.next_u64() % n
+    }
+
+    // Uniform value in [lo, hi]
+    pub fn between(&mut self, lo: i64, hi: i64) -> i64 {
+        lo + self.below((hi - lo + 1) as u64) as i64
+    }
+
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+pub fn generate(day: u8, size: usize, seed: u64) -> Result<String> {
+    let mut rng = Rng::new(seed);
+    let size = size.max(1);
+    let input = match day {
+        1 => day01(&mut rng, size),
+        2 => day02(&mut rng, size),
+        3 => day03(&mut rng, size),
+        4 => day04(&mut rng, size),
+        5 => day05(&mut rng, size),
+        6 => day06(&mut rng, size),
+        7 => day07(&mut rng, size),
+        8 => day08(&mut rng, size),
+        9 => day09(&mut rng, size),
+        10 => day10(&mut rng, size),
+        11 => day11(&mut rng, size),
+        12 => day12(&mut rng, size),
+        _ => bail!("No generator for day {}", day),
+    };
+    Ok(input)
+}
+
+fn char_grid(rng: &mut Rng, width: usize, height: usize, cell: &dyn Fn(&mut Rng) -> char) -> String {
+    (0..height)
+        .map(|_| (0..width).map(|_| cell(rng)).collect::<String>() + "\n")
+        .collect()
+}
+
+// Increasing values in [lo, hi], with no two equal
+fn distinct_sorted(rng: &mut Rng, n: usize, lo: i64, hi: i64) -> Vec<i64> {
+    let mut values: HashSet<i64> = HashSet::new();
+    while values.len() < n {
+        values.insert(rng.between(lo, hi));
+    }
+    let mut values: Vec<i64> = values.into_iter().collect();
+    values.sort_unstable();
+    values
+}
+
+// Dial rotations, one per line
+fn day01(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let direction = if rng.chance(1, 2) { 'L' } else { 'R' };
+            format!("{}{}\n", direction, rng.between(1, 999))
+        })
+        .collect()
+}
+
+// Comma separated ID ranges, kept narrow so brute force stays cheap
+fn day02(rng: &mut Rng, size: usize) -> String {
+    let ranges: Vec<String> = (0..size)
+        .map(|_| {
+            let digits = rng.between(1, 10) as u32;
+            let start = rng.between(10i64.pow(digits - 1), 10i64.pow(digits) - 1);
+            let end = start + rng.between(0, 1000);
+            format!("{}-{}", start, end)
+        })
+        .collect();
+    ranges.join(",") + "\n"
+}
+
+// Battery banks, at least 12 digits wide
+fn day03(rng: &mut Rng, size: usize) -> String {
+    char_grid(rng, size.max(12), size, &|rng| {
+        char::from_digit(rng.between(1, 9) as u32, 10).unwrap()
+    })
+}
+
+// Paper rolls
+fn day04(rng: &mut Rng, size: usize) -> String {
+    char_grid(rng, size, size, &|rng| if rng.chance(3, 5) { '@' } else { '.' })
+}
+
+// Fresh ranges, a blank line and ingredient IDs
+fn day05(rng: &mut Rng, size: usize) -> String {
+    let max_id = (size * 20) as i64;
+    let mut input = String::new();
+    for _ in 0..size {
+        let start = rng.between(1, max_id);
+        input += &format!("{}-{}\n", start, start + rng.between(0, max_id / 4));
+    }
+    input += "\n";
+    for _ in 0..size {
+        input += &format!("{}\n", rng.between(1, max_id));
+    }
+    input
+}
+
+// Worksheet problems laid out in columns, with the operators on the last line
+fn day06(rng: &mut Rng, size: usize) -> String {
+    let n_rows = rng.between(2, 4) as usize;
+    let mut lines: Vec<String> = vec![String::new(); n_rows + 1];
+
+    for problem in 0..size {
+        let width = rng.between(1, 4) as usize;
+        // One number always takes the full width so no column of the problem is blank
+        let full = rng.below(n_rows as u64) as usize;
+        let left_aligned = rng.chance(1, 2);
+        for (row, line) in lines.iter_mut().take(n_rows).enumerate() {
+            let digits = if row == full { width } else { rng.between(1, width as i64) as usize };
+            let number = rng.between(10i64.pow(digits as u32 - 1), 10i64.pow(digits as u32) - 1);
+            if problem > 0 {
+                line.push(' ');
+            }
+            if left_aligned {
+                line.push_str(&format!("{:<width$}", number, width = width));
+            } else {
+                line.push_str(&format!("{:>width$}", number, width = width));
+            }
+        }
+        let operation = if rng.chance(1, 2) { '+' } else { '*' };
+        if problem > 0 {
+            lines[n_rows].push(' ');
+        }
+        lines[n_rows].push_str(&format!("{:<width$}", operation, width = width));
+    }
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
+
+// Tachyon manifold: start on the top row, splitters on every other row, never touching
+fn day07(rng: &mut Rng, size: usize) -> String {
+    let width = 2 * size + 1;
+    let height = 2 * size;
+    let mut rows: Vec<Vec<char>> = vec![vec!['.'; width]; height];
+    rows[0][width / 2] = 'S';
+    for row in rows.iter_mut().skip(2).step_by(2) {
+        for x in 1..width - 1 {
+            if row[x - 1] != '^' && rng.chance(1, 3) {
+                row[x] = '^';
+            }
+        }
+    }
+    rows.iter()
+        .map(|row| row.iter().collect::<String>() + "\n")
+        .collect()
+}
+
+// Distinct junction boxes in 3D space
+fn day08(rng: &mut Rng, size: usize) -> String {
+    let limit = (size * 100) as i64;
+    let mut seen: HashSet<(i64, i64, i64)> = HashSet::new();
+    let mut input = String::new();
+    while seen.len() < size {
+        let point = (rng.between(0, limit), rng.between(0, limit), rng.between(0, limit));
+        if seen.insert(point) {
+            input += &format!("{},{},{}\n", point.0, point.1, point.2);
+        }
+    }
+    input
+}
+
+// Uniform value in [lo, hi] other than `skip`
+fn between_except(rng: &mut Rng, lo: i64, hi: i64, skip: i64) -> i64 {
+    if skip < lo || skip > hi {
+        return rng.between(lo, hi);
+    }
+    let value = rng.between(lo, hi - 1);
+    if value >= skip { value + 1 } else { value }
+}
+
+// Closed rectilinear polygon, listed vertex by vertex.
+// The shape is x-monotone: every column band has its own bottom and top, and
+// neighbouring bands overlap so the outline never touches itself.
+// Bottoms stay below `limit - 1` and tops above 1, so the next band always has
+// a bottom and a top that overlap the previous band without sharing its bounds.
+fn day09(rng: &mut Rng, size: usize) -> String {
+    let bands = size.max(1);
+    let limit = (bands * 10) as i64;
+    let xs = distinct_sorted(rng, bands + 1, 0, limit);
+
+    let mut bottoms: Vec<i64> = Vec::with_capacity(bands);
+    let mut tops: Vec<i64> = Vec::with_capacity(bands);
+    for band in 0..bands {
+        let (bottom, top) = if band == 0 {
+            let bottom = rng.between(0, limit - 2);
+            (bottom, rng.between((bottom + 1).max(2), limit))
+        } else {
+            let (prev_bottom, prev_top) = (bottoms[band - 1], tops[band - 1]);
+            let bottom = between_except(rng, 0, (prev_top - 1).min(limit - 2), prev_bottom);
+            let lowest_top = (bottom.max(prev_bottom) + 1).max(2);
+            (bottom, between_except(rng, lowest_top, limit, prev_top))
+        };
+        bottoms.push(bottom);
+        tops.push(top);
+    }
+
+    let mut vertices: Vec<(i64, i64)> = Vec::new();
+    for band in 0..bands {
+        vertices.push((xs[band], tops[band]));
+        vertices.push((xs[band + 1], tops[band]));
+    }
+    for band in (0..bands).rev() {
+        vertices.push((xs[band + 1], bottoms[band]));
+        vertices.push((xs[band], bottoms[band]));
+    }
+    vertices
+        .iter()
+        .map(|(x, y)| format!("{},{}\n", x, y))
+        .collect()
+}
+
+// Machines whose light pattern and joltages are reachable from the listed buttons.
+// Small sizes also keep the number of buttons and presses small.
+fn day10(rng: &mut Rng, size: usize) -> String {
+    let max_buttons = (size as i64 + 2).min(8);
+    let max_presses = (size as i64).min(12);
+    (0..size)
+        .map(|_| {
+            let n_lights = rng.between(2, 8) as usize;
+            let n_buttons = rng.between(2, max_buttons) as usize;
+            let buttons: Vec<Vec<usize>> = (0..n_buttons)
+                .map(|_| {
+                    let mut lights: Vec<usize> = (0..n_lights).collect();
+                    rng.shuffle(&mut lights);
+                    lights.truncate(rng.between(1, n_lights as i64) as usize);
+                    lights.sort_unstable();
+                    lights
+                })
+                .collect();
+
+            let mut wiring = vec!['.'; n_lights];
+            let mut joltage = vec![0; n_lights];
+            for button in &buttons {
+                if rng.chance(1, 2) {
+                    for &light in button {
+                        wiring[light] = if wiring[light] == '.' { '#' } else { '.' };
+                    }
+                }
+                let presses = rng.between(0, max_presses);
+                for &light in button {
+                    joltage[light] += presses;
+                }
+            }
+
+            let buttons: Vec<String> = buttons
+                .iter()
+                .map(|b| format!("({})", b.iter().map(|l| l.to_string()).collect::<Vec<_>>().join(",")))
+                .collect();
+            let joltage: Vec<String> = joltage.iter().map(|j| j.to_string()).collect();
+            format!(
+                "[{}] {} {{{}}}\n",
+                wiring.iter().collect::<String>(),
+                buttons.join(" "),
+                joltage.join(",")
+            )
+        })
+        .collect()
+}
+
+// Device graph without cycles; edges always point forward in a hidden order
+fn day11(rng: &mut Rng, size: usize) -> String {
+    const SPECIAL: [&str; 5] = ["svr", "you", "dac", "fft", "out"];
+    let mut names: HashSet<String> = SPECIAL.iter().map(|s| s.to_string()).collect();
+    let mut middle: Vec<String> = vec!["you".to_string(), "dac".to_string(), "fft".to_string()];
+    // Three letters like the puzzle, longer once the names would run out
+    let wanted = (size + 8).saturating_mul(4);
+    let len = (3..).find(|&len| 26usize.checked_pow(len).is_none_or(|n| n >= wanted)).unwrap();
+    while middle.len() < size + 3 {
+        let name: String = (0..len)
+            .map(|_| (b'a' + rng.below(26) as u8) as char)
+            .collect();
+        if names.insert(name.clone()) {
+            middle.push(name);
+        }
+    }
+    rng.shuffle(&mut middle);
+
+    let mut order = vec!["svr".to_string()];
+    order.append(&mut middle);
+    order.push("out".to_string());
+
+    let mut input = String::new();
+    for (i, node) in order.iter().enumerate().take(order.len() - 1) {
+        let later = &order[i + 1..];
+        let n_edges = rng.between(1, later.len().min(3) as i64) as usize;
+        // Drawn one at a time, since shuffling every later device is quadratic
+        let mut targets: Vec<&str> = Vec::with_capacity(n_edges);
+        while targets.len() < n_edges {
+            let target = rng.choose(later).as_str();
+            if !targets.contains(&target) {
+                targets.push(target);
+            }
+        }
+        input += &format!("{}: {}\n", node, targets.join(" "));
+    }
+    input
+}
+
+// Six 3x3 present shapes followed by `size` regions, growing with the size
+fn day12(rng: &mut Rng, size: usize) -> String {
+    let max_side = (3 + 2 * size as i64).min(50);
+    const SHAPES: usize = 6;
+    let mut input = String::new();
+    for id in 0..SHAPES {
+        input += &format!("{}:\n", id);
+        loop {
+            let shape = char_grid(rng, 3, 3, &|rng| if rng.chance(2, 3) { '#' } else { '.' });
+            if shape.contains('#') {
+                input += &shape;
+                break;
+            }
+        }
+        input += "\n";
+    }
+    for _ in 0..size {
+        let width = rng.between(3, max_side);
+        let height = rng.between(3, max_side);
+        let counts: Vec<String> = (0..SHAPES)
+            .map(|_| rng.between(0, width * height / 27).to_string())
+            .collect();
+        input += &format!("{}x{}: {}\n", width, height, counts.join(" "));
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::day09;
+    use crate::generator::generate;
+
+    #[test]
+    fn test_generate_is_deterministic() {
+        for day in 1..=12 {
+            assert_eq!(generate(day, 8, 42).unwrap(), generate(day, 8, 42).unwrap());
+            assert_ne!(generate(day, 8, 42).unwrap(), generate(day, 8, 43).unwrap());
+        }
+        assert!(generate(13, 8, 42).is_err());
+    }
+
+    #[test]
+    fn test_day09_polygon_is_closed_and_rectilinear() {
+        for seed in 0..50 {
+            let input = generate(9, 6, seed).unwrap();
+            let vertices: Vec<(i64, i64)> = input
+                .lines()
+                .map(|line| {
+                    let (x, y) = line.split_once(',').unwrap();
+                    (x.parse().unwrap(), y.parse().unwrap())
+                })
+                .collect();
+            for i in 0..vertices.len() {
+                let (a, b) = (vertices[i], vertices[(i + 1) % vertices.len()]);
+                assert!(a != b && (a.0 == b.0 || a.1 == b.1));
+            }
+        }
+    }
+
+    #[test]
+    fn test_day09_polygon_for_any_size_and_seed() {
+        for size in 1..=12 {
+            for seed in 0..200 {
+                let input = generate(9, size, seed).unwrap();
+                assert_eq!(input.lines().count(), 4 * size);
+                if size <= 3 {
+                    assert!(day09::reference_answers(&input).is_ok(), "size {} seed {}", size, seed);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_day11_large_sizes_finish() {
+        let input = generate(11, 20_000, 3).unwrap();
+        assert_eq!(input.lines().count(), 20_004);
+        assert!(crate::days::parse_input(11, &input).is_ok());
+    }
+}
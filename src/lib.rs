@@ -0,0 +1,7 @@
+pub mod answer;
+pub mod bignum;
+pub mod board;
+pub mod days;
+pub mod difftest;
+pub mod generator;
+pub mod point;
@@ -1,24 +1,79 @@
-use clap::Parser;
+use aoc25::answer::Answer;
+use aoc25::{days, difftest, generator};
+use clap::{Parser, Subcommand};
 use anyhow::Result;
 
-mod days;
-mod board;
-
 #[derive(Parser)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Cli {
-    #[arg(short, long, value_name = "DAY")]
-    day: u8,
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[arg(short, long, value_name = "DAY", required = true)]
+    day: Option<u8>,
 
-    #[arg(short, long, value_name = "PART")]
+    #[arg(short, long, value_name = "PART", value_parser = clap::value_parser!(u8).range(1..=2))]
     part: Option<u8>,
+
+    /// Print the answers as a JSON object
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print a random, syntactically valid input for a day
+    Gen {
+        #[arg(short, long, value_name = "DAY")]
+        day: u8,
+
+        #[arg(short, long, value_name = "SIZE", default_value_t = 10)]
+        size: usize,
+
+        #[arg(long, value_name = "SEED", default_value_t = 0)]
+        seed: u64,
+    },
+    /// Compare a day's solvers against slow reference implementations on generated inputs
+    DiffTest {
+        #[arg(short, long, value_name = "DAY")]
+        day: u8,
+
+        #[arg(short, long, value_name = "CASES", default_value_t = 200)]
+        cases: usize,
+
+        #[arg(short, long, value_name = "SIZE", default_value_t = 5)]
+        size: usize,
+
+        #[arg(long, value_name = "SEED", default_value_t = 0)]
+        seed: u64,
+    },
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
-    println!("Advent of Code - DAY {} ", cli.day);
+    match cli.command {
+        Some(Command::Gen { day, size, seed }) => {
+            print!("{}", generator::generate(day, size, seed)?);
+            return Ok(());
+        }
+        Some(Command::DiffTest { day, cases, size, seed }) => {
+            match difftest::run(day, cases, size, seed)? {
+                Some(mismatch) => {
+                    println!("Mismatch on part {} (seed {})", mismatch.part, mismatch.seed);
+                    println!("Fast:      {}", mismatch.fast);
+                    println!("Reference: {}", mismatch.reference);
+                    print!("Input:\n{}", mismatch.input);
+                }
+                None => println!("Day {}: {} cases agree", day, cases),
+            }
+            return Ok(());
+        }
+        None => {}
+    }
 
-    match cli.day {
+    let day = cli.day.unwrap();
+    let answers = match day {
         1 => days::day01::solve()?,
         2 => days::day02::solve()?,
         3 => days::day03::solve()?,
@@ -31,7 +86,29 @@ fn main() -> Result<()> {
         10 => days::day10::solve()?,
         11 => days::day11::solve()?,
         12 => days::day12::solve()?,
-        _ => println!("Day {} not implemented.", cli.day),
+        _ => {
+            println!("Day {} not implemented.", day);
+            return Ok(());
+        }
+    };
+
+    let parts: Vec<(usize, &Answer)> = answers
+        .iter()
+        .enumerate()
+        .map(|(i, answer)| (i + 1, answer))
+        .filter(|(part, _)| cli.part.is_none_or(|p| p as usize == *part))
+        .collect();
+
+    if cli.json {
+        let fields: Vec<String> = std::iter::once(format!("\"day\": {}", day))
+            .chain(parts.iter().map(|(part, answer)| format!("\"part{}\": {}", part, answer.to_json())))
+            .collect();
+        println!("{{{}}}", fields.join(", "));
+    } else {
+        println!("Advent of Code - DAY {} ", day);
+        for (part, answer) in parts {
+            println!("Part {}: {}", part, answer);
+        }
     }
 
     Ok(())
@@ -0,0 +1,217 @@
+use anyhow::{bail, Context, Error, Result};
+use std::fmt::{Debug, Display, Formatter};
+use std::hash::Hash;
+use std::num::ParseIntError;
+use std::ops::{Add, Mul, Neg, Sub};
+use std::str::FromStr;
+
+// Integer types a point can be made of
+pub trait Component:
+    Copy
+    + Debug
+    + Display
+    + Default
+    + Ord
+    + Hash
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+    + FromStr<Err = ParseIntError>
+{
+    fn to_i64(self) -> i64;
+}
+
+impl Component for i16 {
+    fn to_i64(self) -> i64 {
+        self as i64
+    }
+}
+
+impl Component for i32 {
+    fn to_i64(self) -> i64 {
+        self as i64
+    }
+}
+
+impl Component for i64 {
+    fn to_i64(self) -> i64 {
+        self
+    }
+}
+
+// Point in D dimensions. Points are ordered by their first component, then the second...
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct Point<N, const D: usize> {
+    pub coords: [N; D],
+}
+
+impl<N: Component, const D: usize> Point<N, D> {
+    pub fn from_array(coords: [N; D]) -> Self {
+        Self { coords }
+    }
+
+    pub fn zero() -> Self {
+        Self::from_array([N::default(); D])
+    }
+
+    fn zip(self, other: Self, op: impl Fn(N, N) -> N) -> Self {
+        Self::from_array(std::array::from_fn(|i| op(self.coords[i], other.coords[i])))
+    }
+
+    fn map(self, op: impl Fn(N) -> N) -> Self {
+        Self::from_array(self.coords.map(op))
+    }
+
+    // Components are widened before subtracting, so points at opposite ends of
+    // the i32 range are still a valid distance apart
+    fn abs_diffs(&self, other: &Self) -> impl Iterator<Item = i64> + '_ {
+        let other = *other;
+        (0..D).map(move |i| (self.coords[i].to_i64() - other.coords[i].to_i64()).abs())
+    }
+
+    pub fn manhattan(&self, other: Self) -> i64 {
+        self.abs_diffs(&other).sum()
+    }
+
+    pub fn chebyshev(&self, other: Self) -> i64 {
+        self.abs_diffs(&other).max().unwrap_or_default()
+    }
+
+    // Squared straight-line distance, in i128 since one squared i32 span already fills an i64
+    pub fn euclidean_squared(&self, other: Self) -> i128 {
+        self.abs_diffs(&other).map(|d| d as i128 * d as i128).sum()
+    }
+}
+
+impl<N: Component> Point<N, 2> {
+    pub const fn new(x: N, y: N) -> Self {
+        Self { coords: [x, y] }
+    }
+
+    pub fn x(&self) -> N {
+        self.coords[0]
+    }
+
+    pub fn y(&self) -> N {
+        self.coords[1]
+    }
+}
+
+impl<N: Component> Point<N, 3> {
+    pub const fn new(x: N, y: N, z: N) -> Self {
+        Self { coords: [x, y, z] }
+    }
+
+    pub fn x(&self) -> N {
+        self.coords[0]
+    }
+
+    pub fn y(&self) -> N {
+        self.coords[1]
+    }
+
+    pub fn z(&self) -> N {
+        self.coords[2]
+    }
+}
+
+impl<N: Component, const D: usize> Add for Point<N, D> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.zip(rhs, |a, b| a + b)
+    }
+}
+
+impl<N: Component, const D: usize> Sub for Point<N, D> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.zip(rhs, |a, b| a - b)
+    }
+}
+
+// Component-wise product
+impl<N: Component, const D: usize> Mul for Point<N, D> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.zip(rhs, |a, b| a * b)
+    }
+}
+
+impl<N: Component, const D: usize> Mul<N> for Point<N, D> {
+    type Output = Self;
+
+    fn mul(self, rhs: N) -> Self::Output {
+        self.map(|a| a * rhs)
+    }
+}
+
+impl<N: Component, const D: usize> Neg for Point<N, D> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.map(|a| -a)
+    }
+}
+
+// Written as "x,y" or "x,y,z", the same text `FromStr` reads
+impl<N: Component, const D: usize> Display for Point<N, D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let parts: Vec<String> = self.coords.iter().map(|c| c.to_string()).collect();
+        write!(f, "{}", parts.join(","))
+    }
+}
+
+impl<N: Component, const D: usize> FromStr for Point<N, D> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let parts: Vec<N> = s
+            .split(',')
+            .map(|part| part.trim().parse().with_context(|| format!("Invalid coordinate in {:?}", s)))
+            .collect::<Result<_>>()?;
+        let Ok(coords) = parts.try_into() else {
+            bail!("Expected {} comma separated coordinates: {:?}", D, s);
+        };
+        Ok(Self::from_array(coords))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::point::Point;
+
+    #[test]
+    fn test_point_arithmetic_and_distances() {
+        let a = Point::<i64, 3>::new(1, -2, 3);
+        let b = Point::<i64, 3>::new(4, 2, 3);
+        assert_eq!(a + b, Point::<i64, 3>::new(5, 0, 6));
+        assert_eq!(b - a, Point::<i64, 3>::new(3, 4, 0));
+        assert_eq!(-a * 2, Point::<i64, 3>::new(-2, 4, -6));
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(a.euclidean_squared(b), 25);
+        assert!(Point::<i32, 2>::new(1, 9) < Point::<i32, 2>::new(2, 0));
+
+        let far = Point::<i32, 3>::new(100_000, 100_000, 100_000);
+        assert_eq!(far.euclidean_squared(Point::zero()), 30_000_000_000);
+
+        let (low, high) = (Point::<i32, 2>::new(i32::MIN, i32::MIN), Point::<i32, 2>::new(i32::MAX, 0));
+        assert_eq!(low.manhattan(high), 4_294_967_295 + 2_147_483_648);
+        assert_eq!(low.chebyshev(high), 4_294_967_295);
+        assert_eq!(low.euclidean_squared(high), 4_294_967_295i128.pow(2) + 2_147_483_648i128.pow(2));
+    }
+
+    #[test]
+    fn test_point_parsing() {
+        let p: Point<i32, 3> = "162, 817,812".parse().unwrap();
+        assert_eq!((p.x(), p.y(), p.z()), (162, 817, 812));
+        assert_eq!(p.to_string().parse::<Point<i32, 3>>().unwrap(), p);
+        assert_eq!("7,-1".parse::<Point<i32, 2>>().unwrap(), Point::<i32, 2>::new(7, -1));
+        assert!("1,2".parse::<Point<i32, 3>>().is_err());
+        assert!("1,a".parse::<Point<i32, 2>>().is_err());
+    }
+}